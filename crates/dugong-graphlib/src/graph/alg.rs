@@ -93,6 +93,51 @@ where
     out
 }
 
+/// Returns `true` when the graph has no cycle, self-loops included.
+///
+/// Mirrors graphlib's `isAcyclic` (a topsort that fails on a cycle), without collecting the
+/// cycles like [`find_cycles`]. Edges are followed from `v` to `w`.
+pub fn is_acyclic<N, E, G>(g: &Graph<N, E, G>) -> bool
+where
+    N: Default + 'static,
+    E: Default + 'static,
+    G: Default,
+{
+    let mut slots = 0usize;
+    g.for_each_node_ix(|v_ix, _, _| slots = slots.max(v_ix + 1));
+
+    let mut in_degree = vec![0usize; slots];
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); slots];
+    let mut has_self_loop = false;
+    g.for_each_edge_ix(|v_ix, w_ix, _, _| {
+        has_self_loop |= v_ix == w_ix;
+        in_degree[w_ix] += 1;
+        successors[v_ix].push(w_ix);
+    });
+    if has_self_loop {
+        return false;
+    }
+
+    // Kahn's algorithm: every node is removed exactly when the graph is acyclic.
+    let mut ready: Vec<usize> = Vec::new();
+    g.for_each_node_ix(|v_ix, _, _| {
+        if in_degree[v_ix] == 0 {
+            ready.push(v_ix);
+        }
+    });
+    let mut removed = 0usize;
+    while let Some(v_ix) = ready.pop() {
+        removed += 1;
+        for &w_ix in &successors[v_ix] {
+            in_degree[w_ix] -= 1;
+            if in_degree[w_ix] == 0 {
+                ready.push(w_ix);
+            }
+        }
+    }
+    removed == g.node_count()
+}
+
 pub fn find_cycles<N, E, G>(g: &Graph<N, E, G>) -> Vec<Vec<String>>
 where
     N: Default + 'static,
@@ -30,6 +30,23 @@ impl fmt::Display for GraphError {
 
 impl Error for GraphError {}
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    MissingNode(String),
+    NodeExists(String),
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingNode(id) => write!(f, "Cannot rename missing node {id:?}"),
+            Self::NodeExists(id) => write!(f, "Cannot rename to {id:?}: node already exists"),
+        }
+    }
+}
+
+impl Error for RenameError {}
+
 pub struct Graph<N, E, G>
 where
     N: Default + 'static,
@@ -132,6 +149,19 @@ where
         true
     }
 
+    /// Releases spare capacity in the node/edge slot vectors and their index maps.
+    ///
+    /// Slot indices are unchanged (tombstones stay in place); call
+    /// [`Graph::compact_if_sparse`] first to drop those as well.
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.node_index.shrink_to_fit();
+        self.edges.shrink_to_fit();
+        self.edge_index.shrink_to_fit();
+        self.parent_ix.shrink_to_fit();
+        self.children_ix.shrink_to_fit();
+    }
+
     fn compact(&mut self) {
         self.invalidate_adj();
 
@@ -609,6 +639,7 @@ where
         self
     }
 
+    /// Adds `id` with the default node label if it is new; an existing node keeps its label.
     pub fn ensure_node(&mut self, id: impl Into<String>) -> &mut Self {
         let id = id.into();
         if self.node_index.contains_key(&id) {
@@ -618,6 +649,11 @@ where
         self.set_node(id, label)
     }
 
+    /// Graphlib's label-less `setNode(v)`; an alias for [`Graph::ensure_node`].
+    pub fn set_node_default(&mut self, id: impl Into<String>) -> &mut Self {
+        self.ensure_node(id)
+    }
+
     pub fn ensure_node_ref(&mut self, id: &str) -> &mut Self {
         if self.node_index.contains_key(id) {
             return self;
@@ -642,6 +678,11 @@ where
             .map(|n| &mut n.label)
     }
 
+    /// Looks up several nodes at once, in the order of `ids`; missing ids map to `None`.
+    pub fn nodes_by_ids<'a>(&'a self, ids: &'a [String]) -> Vec<(&'a str, Option<&'a N>)> {
+        ids.iter().map(|id| (id.as_str(), self.node(id))).collect()
+    }
+
     pub fn node_count(&self) -> usize {
         self.node_len
     }
@@ -652,6 +693,11 @@ where
             .filter_map(|n| n.as_ref().map(|n| n.id.as_str()))
     }
 
+    /// Returns an owned snapshot of every node id in insertion order.
+    ///
+    /// Needed by passes that mutate the graph while walking it (e.g. network simplex writing
+    /// ranks back with `node_mut`, or Dagre's `assignRankMinMax`). Read-only scans should use
+    /// [`Graph::node_ids_ref`] instead and clone only the ids they keep.
     pub fn node_ids(&self) -> Vec<String> {
         self.nodes
             .iter()
@@ -659,6 +705,11 @@ where
             .collect()
     }
 
+    /// Borrowing counterpart of [`Graph::node_ids`]; same order, no `String` clones.
+    pub fn node_ids_ref(&self) -> impl Iterator<Item = &str> {
+        self.nodes()
+    }
+
     pub fn edge_count(&self) -> usize {
         self.edge_len
     }
@@ -670,6 +721,7 @@ where
             .map(|e| &e.key)
     }
 
+    /// Iterates edge keys in insertion order; see [`Graph::edge_keys`].
     pub fn edges(&self) -> impl Iterator<Item = &EdgeKey> {
         self.edges.iter().filter_map(|e| e.as_ref().map(|e| &e.key))
     }
@@ -758,6 +810,12 @@ where
         }
     }
 
+    /// Returns every edge key in insertion order.
+    ///
+    /// The order comes from the edge slot vector, never from the key index map, so it is stable
+    /// across runs. Removing an edge leaves the others in place, re-adding it appends it at the
+    /// end, and compaction keeps the order. Layout passes that iterate and mutate edges rely on
+    /// this for reproducible output.
     pub fn edge_keys(&self) -> Vec<EdgeKey> {
         self.edges
             .iter()
@@ -765,6 +823,14 @@ where
             .collect()
     }
 
+    /// Borrowing counterpart of [`Graph::edge_keys`]; same order, no `EdgeKey` clones.
+    ///
+    /// Use this for read-only scans. Passes that remove or re-add edges while iterating (e.g.
+    /// `acyclic`) still need the owned snapshot from [`Graph::edge_keys`].
+    pub fn edge_keys_ref(&self) -> impl Iterator<Item = &EdgeKey> {
+        self.edges()
+    }
+
     pub fn filter_nodes<F>(&self, mut filter: F) -> Self
     where
         N: Clone,
@@ -809,6 +875,193 @@ where
         copy
     }
 
+    /// Returns a copy of this graph keeping every node (and parent link) but only the edges for
+    /// which `filter` returns `true`.
+    pub fn filter_edges<F>(&self, mut filter: F) -> Self
+    where
+        N: Clone,
+        E: Clone,
+        G: Clone,
+        F: FnMut(&EdgeKey, &E) -> bool,
+    {
+        let mut copy = Self::new(self.options);
+        copy.set_graph(self.graph_label.clone());
+
+        for node in self.nodes.iter().filter_map(|n| n.as_ref()) {
+            copy.set_node(node.id.clone(), node.label.clone());
+        }
+
+        if self.options.compound {
+            for node in self.nodes.iter().filter_map(|n| n.as_ref()) {
+                if let Some(parent_id) = self.parent(&node.id) {
+                    copy.set_parent_ref(&node.id, parent_id);
+                }
+            }
+        }
+
+        for edge in self.edges.iter().filter_map(|e| e.as_ref()) {
+            if filter(&edge.key, &edge.label) {
+                copy.set_edge_named(
+                    edge.key.v.clone(),
+                    edge.key.w.clone(),
+                    edge.key.name.clone(),
+                    Some(edge.label.clone()),
+                );
+            }
+        }
+
+        copy
+    }
+
+    /// Returns an undirected copy of this graph (`directed: false`, other options kept).
+    ///
+    /// Edges are re-keyed with graphlib's undirected endpoint ordering, so `a -> b` and `b -> a`
+    /// (with the same name) collapse into one edge whose label is `combine(first, second)`, in
+    /// edge insertion order. Nodes, parents and the graph label are copied unchanged.
+    pub fn to_undirected<F>(&self, mut combine: F) -> Self
+    where
+        N: Clone,
+        E: Clone,
+        G: Clone,
+        F: FnMut(&E, &E) -> E,
+    {
+        let mut copy = Self::new(GraphOptions {
+            directed: false,
+            ..self.options
+        });
+        copy.set_graph(self.graph_label.clone());
+
+        for node in self.nodes.iter().filter_map(|n| n.as_ref()) {
+            copy.set_node(node.id.clone(), node.label.clone());
+        }
+
+        if self.options.compound {
+            for node in self.nodes.iter().filter_map(|n| n.as_ref()) {
+                if let Some(parent_id) = self.parent(&node.id) {
+                    copy.set_parent_ref(&node.id, parent_id);
+                }
+            }
+        }
+
+        for edge in self.edges.iter().filter_map(|e| e.as_ref()) {
+            let key = &edge.key;
+            let label = match copy.edge(&key.v, &key.w, key.name.as_deref()) {
+                Some(existing) => combine(existing, &edge.label),
+                None => edge.label.clone(),
+            };
+            copy.set_edge_named(key.v.clone(), key.w.clone(), key.name.clone(), Some(label));
+        }
+
+        copy
+    }
+
+    /// Returns a structural copy of this graph with every node, edge, and graph label transformed.
+    ///
+    /// Node ids, edge keys, insertion order, parent/child relationships, and options are
+    /// preserved. Default node/edge label factories are not carried over; the returned graph
+    /// falls back to `N2::default()` / `E2::default()`.
+    pub fn map<N2, E2, G2, FN, FE, FG>(
+        &self,
+        mut node_fn: FN,
+        mut edge_fn: FE,
+        graph_fn: FG,
+    ) -> Graph<N2, E2, G2>
+    where
+        N2: Default + 'static,
+        E2: Default + 'static,
+        G2: Default,
+        FN: FnMut(&str, &N) -> N2,
+        FE: FnMut(&EdgeKey, &E) -> E2,
+        FG: FnOnce(&G) -> G2,
+    {
+        let mut copy = Graph::<N2, E2, G2>::new(self.options);
+        copy.graph_label = graph_fn(&self.graph_label);
+        copy.nodes = self
+            .nodes
+            .iter()
+            .map(|slot| {
+                slot.as_ref().map(|node| NodeEntry {
+                    id: node.id.clone(),
+                    label: node_fn(&node.id, &node.label),
+                })
+            })
+            .collect();
+        copy.node_len = self.node_len;
+        copy.node_index = self.node_index.clone();
+        copy.edges = self
+            .edges
+            .iter()
+            .map(|slot| {
+                slot.as_ref().map(|edge| EdgeEntry {
+                    key: edge.key.clone(),
+                    v_ix: edge.v_ix,
+                    w_ix: edge.w_ix,
+                    label: edge_fn(&edge.key, &edge.label),
+                })
+            })
+            .collect();
+        copy.edge_len = self.edge_len;
+        copy.edge_index = self.edge_index.clone();
+        copy.parent_ix = self.parent_ix.clone();
+        copy.children_ix = self.children_ix.clone();
+        copy
+    }
+
+    /// Like [`Graph::map`], but only transforms node labels.
+    pub fn map_nodes<N2, F>(&self, f: F) -> Graph<N2, E, G>
+    where
+        N2: Default + 'static,
+        E: Clone,
+        G: Clone,
+        F: FnMut(&str, &N) -> N2,
+    {
+        self.map(f, |_, label| label.clone(), G::clone)
+    }
+
+    /// Like [`Graph::map`], but only transforms edge labels.
+    pub fn map_edges<E2, F>(&self, f: F) -> Graph<N, E2, G>
+    where
+        E2: Default + 'static,
+        N: Clone,
+        G: Clone,
+        F: FnMut(&EdgeKey, &E) -> E2,
+    {
+        self.map(|_, label| label.clone(), f, G::clone)
+    }
+
+    /// Returns node ids (in insertion order) and a dense weight matrix indexed by that order.
+    ///
+    /// `matrix[i][j]` is the sum of `weight_fn` over all edges `i -> j` (parallel multigraph
+    /// edges are summed), or `None` when there is no such edge. Undirected graphs produce a
+    /// symmetric matrix.
+    pub fn to_adjacency_matrix<F>(&self, mut weight_fn: F) -> (Vec<String>, Vec<Vec<Option<f64>>>)
+    where
+        F: FnMut(&EdgeKey, &E) -> f64,
+    {
+        let mut dense_ix: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut ids: Vec<String> = Vec::with_capacity(self.node_len);
+        for (slot_ix, node) in self.nodes.iter().enumerate() {
+            if let Some(node) = node {
+                dense_ix[slot_ix] = Some(ids.len());
+                ids.push(node.id.clone());
+            }
+        }
+
+        let mut matrix: Vec<Vec<Option<f64>>> = vec![vec![None; ids.len()]; ids.len()];
+        for edge in self.edges.iter().filter_map(|e| e.as_ref()) {
+            let (Some(i), Some(j)) = (dense_ix[edge.v_ix], dense_ix[edge.w_ix]) else {
+                continue;
+            };
+            let weight = weight_fn(&edge.key, &edge.label);
+            *matrix[i][j].get_or_insert(0.0) += weight;
+            if !self.options.directed && i != j {
+                *matrix[j][i].get_or_insert(0.0) += weight;
+            }
+        }
+
+        (ids, matrix)
+    }
+
     pub fn set_edge(&mut self, v: impl Into<String>, w: impl Into<String>) -> &mut Self {
         self.set_edge_named(v, w, None::<String>, None)
     }
@@ -847,6 +1100,34 @@ where
         Ok(self.set_edge_canonical(key, label))
     }
 
+    /// Adds an edge `v -> w` named with the smallest free integer (`"0"`, `"1"`, ...) for that
+    /// endpoint pair and returns its key.
+    ///
+    /// On non-multigraphs names are not allowed, so this sets the single unnamed `v -> w` edge
+    /// (replacing its label if it already exists).
+    pub fn add_edge_auto(
+        &mut self,
+        v: impl Into<String>,
+        w: impl Into<String>,
+        label: E,
+    ) -> EdgeKey {
+        let (v, w) = self.canonicalize_endpoints(v.into(), w.into());
+        let mut key = EdgeKey { v, w, name: None };
+        if self.options.multigraph {
+            let mut n: usize = 0;
+            loop {
+                key.name = Some(n.to_string());
+                if !self.edge_index.contains_key(&key) {
+                    break;
+                }
+                n += 1;
+            }
+        }
+
+        self.set_edge_canonical(key.clone(), Some(label));
+        key
+    }
+
     fn set_edge_canonical(&mut self, key: EdgeKey, label: Option<E>) -> &mut Self {
         let v = key.v.clone();
         let w = key.w.clone();
@@ -934,6 +1215,27 @@ where
             .map(|e| &mut e.label)
     }
 
+    /// Looks up the single `v -> w` edge of a simple graph; shorthand for `edge(v, w, None)`.
+    ///
+    /// Debug builds assert the graph is not a multigraph, where a name-less lookup would silently
+    /// ignore named parallel edges.
+    pub fn edge_simple(&self, v: &str, w: &str) -> Option<&E> {
+        debug_assert!(
+            !self.options.multigraph,
+            "edge_simple called on a multigraph; use edge(v, w, name)"
+        );
+        self.edge(v, w, None)
+    }
+
+    /// Mutable counterpart of [`Graph::edge_simple`], with the same multigraph debug assertion.
+    pub fn edge_simple_mut(&mut self, v: &str, w: &str) -> Option<&mut E> {
+        debug_assert!(
+            !self.options.multigraph,
+            "edge_simple_mut called on a multigraph; use edge_mut(v, w, name)"
+        );
+        self.edge_mut(v, w, None)
+    }
+
     pub fn edge_by_key(&self, key: &EdgeKey) -> Option<&E> {
         let view = self.edge_key_view_from_key(key);
         let idx = self.edge_index_of_view(view)?;
@@ -1053,6 +1355,197 @@ where
         true
     }
 
+    /// Renames node `old` to `new`, rewriting every incident edge key.
+    ///
+    /// The node keeps its slot, label, and compound parent/children links (those are stored by
+    /// slot index). In undirected graphs, rewritten keys are re-canonicalized so `v <= w` still
+    /// holds.
+    pub fn rename_node(&mut self, old: &str, new: &str) -> Result<(), RenameError> {
+        let Some(&idx) = self.node_index.get(old) else {
+            return Err(RenameError::MissingNode(old.to_string()));
+        };
+        if old == new {
+            return Ok(());
+        }
+        if self.node_index.contains_key(new) {
+            return Err(RenameError::NodeExists(new.to_string()));
+        }
+
+        let _ = self.node_index.remove(old);
+        self.node_index.insert(new.to_string(), idx);
+        if let Some(Some(entry)) = self.nodes.get_mut(idx) {
+            entry.id = new.to_string();
+        }
+
+        let directed = self.options.directed;
+        for edge in self.edges.iter_mut().filter_map(|e| e.as_mut()) {
+            if edge.v_ix != idx && edge.w_ix != idx {
+                continue;
+            }
+            let _ = self.edge_index.remove_entry(&edge.key);
+            if edge.v_ix == idx {
+                edge.key.v = new.to_string();
+            }
+            if edge.w_ix == idx {
+                edge.key.w = new.to_string();
+            }
+            if !directed && edge.key.v > edge.key.w {
+                std::mem::swap(&mut edge.key.v, &mut edge.key.w);
+                std::mem::swap(&mut edge.v_ix, &mut edge.w_ix);
+            }
+        }
+        for (edge_idx, slot) in self.edges.iter().enumerate() {
+            if let Some(edge) = slot
+                && (edge.v_ix == idx || edge.w_ix == idx)
+            {
+                self.edge_index.insert(edge.key.clone(), edge_idx);
+            }
+        }
+        self.invalidate_adj();
+
+        Ok(())
+    }
+
+    /// Checks the graph's internal invariants and returns every violation found.
+    ///
+    /// Covers: node/edge slots agreeing with `node_index`/`edge_index` and the live counts,
+    /// edge endpoints referring to live nodes whose ids match the edge key, compound
+    /// parent/children links mirroring each other, and parent chains being acyclic. Intended
+    /// for tests and debug assertions after layout passes; it is O(V + E) but not free.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors: Vec<String> = Vec::new();
+        let node_id = |ix: usize| self.nodes.get(ix).and_then(|n| n.as_ref()).map(|n| &n.id);
+
+        let live_nodes = self.nodes.iter().filter(|n| n.is_some()).count();
+        if live_nodes != self.node_len {
+            errors.push(format!(
+                "node count {} does not match {live_nodes} live node slots",
+                self.node_len
+            ));
+        }
+        if self.node_index.len() != live_nodes {
+            errors.push(format!(
+                "node_index has {} entries for {live_nodes} live nodes",
+                self.node_index.len()
+            ));
+        }
+        for (id, &ix) in &self.node_index {
+            if node_id(ix) != Some(id) {
+                errors.push(format!(
+                    "node_index maps {id:?} to slot {ix} holding another node"
+                ));
+            }
+        }
+        for (ix, entry) in self.nodes.iter().enumerate() {
+            if let Some(entry) = entry
+                && self.node_index.get(&entry.id) != Some(&ix)
+            {
+                errors.push(format!(
+                    "node {:?} in slot {ix} is missing from node_index",
+                    entry.id
+                ));
+            }
+        }
+
+        let live_edges = self.edges.iter().filter(|e| e.is_some()).count();
+        if live_edges != self.edge_len {
+            errors.push(format!(
+                "edge count {} does not match {live_edges} live edge slots",
+                self.edge_len
+            ));
+        }
+        if self.edge_index.len() != live_edges {
+            errors.push(format!(
+                "edge_index has {} entries for {live_edges} live edges",
+                self.edge_index.len()
+            ));
+        }
+        for (key, &ix) in &self.edge_index {
+            if self.edges.get(ix).and_then(|e| e.as_ref()).map(|e| &e.key) != Some(key) {
+                errors.push(format!(
+                    "edge_index maps {key:?} to slot {ix} holding another edge"
+                ));
+            }
+        }
+        for (ix, entry) in self.edges.iter().enumerate() {
+            let Some(entry) = entry else {
+                continue;
+            };
+            if self.edge_index.get(&entry.key) != Some(&ix) {
+                errors.push(format!(
+                    "edge {:?} in slot {ix} is missing from edge_index",
+                    entry.key
+                ));
+            }
+            if node_id(entry.v_ix) != Some(&entry.key.v) {
+                errors.push(format!("edge {:?} source is not a live node", entry.key));
+            }
+            if node_id(entry.w_ix) != Some(&entry.key.w) {
+                errors.push(format!("edge {:?} target is not a live node", entry.key));
+            }
+        }
+
+        if self.parent_ix.len() != self.nodes.len() || self.children_ix.len() != self.nodes.len() {
+            errors.push(format!(
+                "parent/children tables have {}/{} slots for {} node slots",
+                self.parent_ix.len(),
+                self.children_ix.len(),
+                self.nodes.len()
+            ));
+        }
+        for (ix, parent) in self.parent_ix.iter().enumerate() {
+            let Some(p) = *parent else {
+                continue;
+            };
+            let Some(id) = node_id(ix) else {
+                errors.push(format!("empty node slot {ix} still has a parent"));
+                continue;
+            };
+            match node_id(p) {
+                None => errors.push(format!("node {id:?} has a parent that is not a live node")),
+                Some(parent_id) => {
+                    if !self.children_ix.get(p).is_some_and(|ch| ch.contains(&ix)) {
+                        errors.push(format!(
+                            "node {id:?} has parent {parent_id:?} that does not list it as a child"
+                        ));
+                    }
+                }
+            }
+        }
+        for (p, children) in self.children_ix.iter().enumerate() {
+            for &c in children {
+                if self.parent_ix.get(c).copied().flatten() != Some(p) {
+                    let parent = node_id(p).map_or("<empty>", |s| s.as_str());
+                    let child = node_id(c).map_or("<empty>", |s| s.as_str());
+                    errors.push(format!(
+                        "{parent:?} lists {child:?} as a child but it has another parent"
+                    ));
+                }
+            }
+        }
+        for (ix, entry) in self.nodes.iter().enumerate() {
+            let Some(entry) = entry else {
+                continue;
+            };
+            let mut cur = self.parent_ix.get(ix).copied().flatten();
+            let mut steps = 0usize;
+            while let Some(p) = cur {
+                steps += 1;
+                if p == ix || steps > self.nodes.len() {
+                    errors.push(format!("node {:?} is its own ancestor", entry.id));
+                    break;
+                }
+                cur = self.parent_ix.get(p).copied().flatten();
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     pub fn successors(&self, v: &str) -> Vec<&str> {
         if !self.options.directed {
             return self.adjacent_nodes(v);
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GraphOptions {
     pub multigraph: bool,
     pub compound: bool,
@@ -11,10 +11,84 @@ pub struct GraphOptions {
 
 impl Default for GraphOptions {
     fn default() -> Self {
+        Self::directed_simple()
+    }
+}
+
+impl GraphOptions {
+    /// Directed, no parallel edges, no parents. Same as [`GraphOptions::default`].
+    pub const fn directed_simple() -> Self {
         Self {
             multigraph: false,
             compound: false,
             directed: true,
         }
     }
+
+    /// Directed graph that allows named parallel edges.
+    pub const fn multigraph() -> Self {
+        Self {
+            multigraph: true,
+            ..Self::directed_simple()
+        }
+    }
+
+    /// Directed graph with parent/child (subgraph) support.
+    pub const fn compound() -> Self {
+        Self {
+            compound: true,
+            ..Self::directed_simple()
+        }
+    }
+
+    /// Directed graph with both parallel edges and parents, as used for Dagre layout input.
+    pub const fn multigraph_compound() -> Self {
+        Self {
+            multigraph: true,
+            compound: true,
+            directed: true,
+        }
+    }
+
+    /// Undirected simple graph.
+    pub const fn undirected() -> Self {
+        Self {
+            directed: false,
+            ..Self::directed_simple()
+        }
+    }
+
+    /// Starts a [`GraphOptionsBuilder`] from the defaults.
+    pub const fn builder() -> GraphOptionsBuilder {
+        GraphOptionsBuilder {
+            options: Self::directed_simple(),
+        }
+    }
+}
+
+/// Step-by-step construction of [`GraphOptions`], starting from [`GraphOptions::default`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GraphOptionsBuilder {
+    options: GraphOptions,
+}
+
+impl GraphOptionsBuilder {
+    pub const fn multigraph(mut self, multigraph: bool) -> Self {
+        self.options.multigraph = multigraph;
+        self
+    }
+
+    pub const fn compound(mut self, compound: bool) -> Self {
+        self.options.compound = compound;
+        self
+    }
+
+    pub const fn directed(mut self, directed: bool) -> Self {
+        self.options.directed = directed;
+        self
+    }
+
+    pub const fn build(self) -> GraphOptions {
+        self.options
+    }
 }
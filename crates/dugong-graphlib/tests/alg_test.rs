@@ -69,6 +69,34 @@ fn components_uses_neighbor_relationships_in_directed_graphs() {
     );
 }
 
+#[test]
+fn is_acyclic_returns_true_for_dag() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
+    g.set_path(&["a", "b", "d"]);
+    g.set_path(&["a", "c", "d"]);
+    g.ensure_node("e");
+
+    assert!(alg::is_acyclic(&g));
+}
+
+#[test]
+fn is_acyclic_returns_false_for_self_loop() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
+    g.set_path(&["a", "b"]);
+    g.set_path(&["b", "b"]);
+
+    assert!(!alg::is_acyclic(&g));
+}
+
+#[test]
+fn is_acyclic_returns_false_for_triangle_cycle() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
+    g.set_path(&["a", "b", "c", "a"]);
+    g.set_path(&["x", "a"]);
+
+    assert!(!alg::is_acyclic(&g));
+}
+
 #[test]
 fn find_cycles_returns_empty_for_empty_graph() {
     let g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
@@ -1,4 +1,4 @@
-use dugong_graphlib::{EdgeKey, Graph, GraphError, GraphOptions};
+use dugong_graphlib::{EdgeKey, Graph, GraphError, GraphOptions, RenameError};
 
 fn sorted(mut values: Vec<&str>) -> Vec<&str> {
     values.sort();
@@ -49,6 +49,64 @@ fn graph_options_can_enable_undirected_compound_or_multigraph_modes() {
     assert!(multigraph.is_multigraph());
 }
 
+#[test]
+fn graph_options_presets_match_struct_literals() {
+    assert_eq!(GraphOptions::directed_simple(), GraphOptions::default());
+    assert_eq!(
+        GraphOptions::multigraph(),
+        GraphOptions {
+            multigraph: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        GraphOptions::compound(),
+        GraphOptions {
+            compound: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        GraphOptions::multigraph_compound(),
+        GraphOptions {
+            multigraph: true,
+            compound: true,
+            directed: true,
+        }
+    );
+    assert_eq!(
+        GraphOptions::undirected(),
+        GraphOptions {
+            directed: false,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn graph_options_builder_starts_from_defaults() {
+    assert_eq!(GraphOptions::builder().build(), GraphOptions::default());
+
+    let options = GraphOptions::builder()
+        .multigraph(true)
+        .compound(true)
+        .directed(false)
+        .build();
+    assert_eq!(
+        options,
+        GraphOptions {
+            multigraph: true,
+            compound: true,
+            directed: false,
+        }
+    );
+
+    let g: Graph<(), (), ()> = Graph::new(options);
+    assert!(g.is_multigraph());
+    assert!(g.is_compound());
+    assert!(!g.is_directed());
+}
+
 #[test]
 fn graph_label_can_be_set_and_read() {
     let mut g: Graph<(), (), Option<String>> = Graph::new(GraphOptions::default());
@@ -67,6 +125,21 @@ fn nodes_returns_inserted_node_ids() {
     assert_eq!(sorted(g.nodes().collect()), vec!["a", "b"]);
 }
 
+#[test]
+fn nodes_by_ids_returns_labels_in_requested_order() {
+    let mut g: Graph<i32, (), ()> = Graph::new(GraphOptions::default());
+    g.set_node("a", 1);
+    g.set_node("b", 2);
+    g.set_node("c", 3);
+
+    let ids = ["c", "missing", "a"].map(String::from);
+
+    assert_eq!(
+        g.nodes_by_ids(&ids),
+        vec![("c", Some(&3)), ("missing", None), ("a", Some(&1))]
+    );
+}
+
 #[test]
 fn sources_returns_nodes_without_in_edges() {
     let mut g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
@@ -151,6 +224,132 @@ fn filter_nodes_preserves_compound_subgraphs_and_promotes_missing_parent() {
     assert_eq!(promoted.parent("a"), Some("root"));
 }
 
+#[test]
+fn filter_edges_drops_edges_below_weight_threshold_and_keeps_nodes() {
+    let mut g: Graph<(), f64, &str> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph("label");
+    g.set_edge_with_label("a", "b", 0.0);
+    g.set_edge_with_label("b", "c", 2.0);
+    g.set_edge_named("a", "c", Some("heavy"), Some(3.0));
+    g.set_edge_named("a", "c", Some("light"), Some(0.5));
+    g.set_parent("a", "cluster");
+
+    let filtered = g.filter_edges(|_, w| *w >= 1.0);
+
+    assert_eq!(filtered.graph(), &"label");
+    assert!(filtered.is_multigraph() && filtered.is_compound());
+    assert_eq!(filtered.node_ids(), g.node_ids());
+    assert_eq!(filtered.parent("a"), Some("cluster"));
+    assert_eq!(
+        filtered.edge_keys(),
+        vec![
+            EdgeKey::new("b", "c", None::<String>),
+            EdgeKey::new("a", "c", Some("heavy")),
+        ]
+    );
+    assert_eq!(filtered.edge("a", "c", Some("heavy")), Some(&3.0));
+    assert!(!filtered.has_edge("a", "b", None));
+    assert_eq!(g.edge_count(), 4);
+}
+
+#[test]
+fn map_nodes_and_map_edges_transform_labels_and_keep_structure() {
+    let mut g: Graph<i32, i32, String> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph("graph label".to_string());
+    g.set_node("a", 1);
+    g.set_node("b", 2);
+    g.set_node("c", 3);
+    g.remove_node("c");
+    g.set_edge_with_label("a", "b", 10);
+    g.set_edge_named("a", "b", Some("named"), Some(20));
+    g.set_parent("b", "parent");
+
+    let nodes = g.map_nodes(|id, label| format!("{id}:{label}"));
+    let edges = g.map_edges(|key, label| (key.name.clone(), label * 2));
+
+    assert!(nodes.is_multigraph());
+    assert!(nodes.is_compound());
+    assert_eq!(nodes.graph(), "graph label");
+    assert_eq!(nodes.node_ids(), vec!["a", "b", "parent"]);
+    assert_eq!(nodes.node("a").map(String::as_str), Some("a:1"));
+    assert_eq!(nodes.parent("b"), Some("parent"));
+    assert_eq!(nodes.edge("a", "b", Some("named")), Some(&20));
+    assert_eq!(nodes.successors("a"), g.successors("a"));
+
+    assert_eq!(edges.node("b"), Some(&2));
+    assert_eq!(edges.edge("a", "b", None), Some(&(None, 20)));
+    assert_eq!(
+        edges.edge("a", "b", Some("named")),
+        Some(&(Some("named".to_string()), 40))
+    );
+    assert_eq!(edges.children("parent"), vec!["b"]);
+}
+
+#[test]
+fn map_transforms_graph_label_and_resets_default_label_factories() {
+    let mut g: Graph<i32, i32, i32> = Graph::new(GraphOptions::default());
+    g.set_graph(5);
+    g.set_default_node_label(|| 7);
+    g.set_default_edge_label(|| 9);
+    g.set_edge("a", "b");
+
+    let mut mapped = g.map(
+        |_, label| i64::from(*label),
+        |_, label| i64::from(*label),
+        |label| label.to_string(),
+    );
+
+    assert_eq!(mapped.graph(), "5");
+    assert_eq!(mapped.node("a"), Some(&7));
+    assert_eq!(mapped.edge("a", "b", None), Some(&9));
+
+    mapped.ensure_node("c");
+    mapped.set_edge("b", "c");
+    assert_eq!(mapped.node("c"), Some(&0));
+    assert_eq!(mapped.edge("b", "c", None), Some(&0));
+}
+
+#[test]
+fn to_adjacency_matrix_sums_parallel_edge_weights_in_node_order() {
+    let mut g: Graph<(), f64, ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge_with_label("a", "b", 1.5);
+    g.set_edge_named("a", "b", Some("again"), Some(2.0));
+    g.set_edge_with_label("b", "c", 4.0);
+    g.set_edge_with_label("c", "c", 0.5);
+    g.ensure_node("d");
+
+    let (ids, matrix) = g.to_adjacency_matrix(|_, w| *w);
+
+    assert_eq!(ids, vec!["a", "b", "c", "d"]);
+    assert_eq!(matrix[0], vec![None, Some(3.5), None, None]);
+    assert_eq!(matrix[1], vec![None, None, Some(4.0), None]);
+    assert_eq!(matrix[2], vec![None, None, Some(0.5), None]);
+    assert_eq!(matrix[3], vec![None, None, None, None]);
+}
+
+#[test]
+fn to_adjacency_matrix_skips_removed_nodes() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions::default());
+    g.set_path(&["a", "b", "c"]);
+    g.remove_node("b");
+
+    let (ids, matrix) = g.to_adjacency_matrix(|_, _| 1.0);
+
+    assert_eq!(ids, vec!["a", "c"]);
+    assert_eq!(matrix, vec![vec![None, None], vec![None, None]]);
+}
+
 #[test]
 fn ensure_node_uses_default_label_for_new_nodes() {
     let mut g: Graph<Option<i32>, (), ()> = Graph::new(GraphOptions::default());
@@ -532,6 +731,30 @@ fn edge_lookup_returns_none_for_missing_edges() {
     assert!(!g.has_edge("a", "b", Some("foo")));
 }
 
+#[test]
+fn edge_simple_looks_up_and_mutates_the_unnamed_edge() {
+    let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions::default());
+    g.set_edge_with_label("a", "b", 7);
+
+    assert_eq!(g.edge_simple("a", "b"), Some(&7));
+    assert_eq!(g.edge_simple("b", "a"), None);
+    *g.edge_simple_mut("a", "b").unwrap() += 1;
+    assert_eq!(g.edge("a", "b", None), Some(&8));
+    assert!(g.edge_simple_mut("a", "c").is_none());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "edge_simple called on a multigraph")]
+fn edge_simple_debug_asserts_on_multigraphs() {
+    let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge_named("a", "b", Some("x"), Some(1));
+    let _ = g.edge_simple("a", "b");
+}
+
 #[test]
 fn edge_lookup_accepts_either_direction_for_undirected_graphs() {
     let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions {
@@ -774,6 +997,39 @@ fn remove_edge_missing_edge_is_noop() {
     assert_eq!(g.edge_count(), 0);
 }
 
+#[test]
+fn add_edge_auto_picks_distinct_integer_names_for_multigraph_pairs() {
+    let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge_named("a", "b", Some("1"), Some(0));
+
+    let keys: Vec<EdgeKey> = (10..13)
+        .map(|label| g.add_edge_auto("a", "b", label))
+        .collect();
+    let other = g.add_edge_auto("b", "a", 20);
+
+    let names: Vec<Option<&str>> = keys.iter().map(|k| k.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("0"), Some("2"), Some("3")]);
+    assert_eq!(g.edge_by_key(&keys[1]), Some(&11));
+    assert_eq!(other.name.as_deref(), Some("0"));
+    assert_eq!(g.edge_count(), 5);
+}
+
+#[test]
+fn add_edge_auto_sets_the_unnamed_edge_on_simple_graphs() {
+    let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions::default());
+
+    let first = g.add_edge_auto("a", "b", 1);
+    let second = g.add_edge_auto("a", "b", 2);
+
+    assert_eq!(first, second);
+    assert_eq!(first.name, None);
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.edge("a", "b", None), Some(&2));
+}
+
 #[test]
 fn remove_edge_key_removes_named_multigraph_edge() {
     let mut g: Graph<(), (), ()> = Graph::new(GraphOptions {
@@ -1009,3 +1265,260 @@ fn node_ids_and_edge_keys_keep_insertion_order_after_removal() {
     assert_eq!(sorted_owned(g.node_ids()), vec!["a", "c", "d"]);
     assert!(g.edge_keys().is_empty());
 }
+
+#[test]
+fn edge_keys_follow_insertion_order() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge("z", "a");
+    g.set_edge_named("b", "c", Some("y"), None);
+    g.set_edge("a", "b");
+    g.set_edge_named("b", "c", Some("x"), None);
+    g.set_edge("c", "a");
+
+    let names = |g: &Graph<(), (), ()>| {
+        g.edge_keys()
+            .into_iter()
+            .map(|k| format!("{}>{}:{}", k.v, k.w, k.name.unwrap_or_default()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&g), vec!["z>a:", "b>c:y", "a>b:", "b>c:x", "c>a:"]);
+    assert!(g.edges().cloned().eq(g.edge_keys()));
+
+    g.remove_edge("a", "b", None);
+    g.set_edge("a", "b");
+    assert_eq!(names(&g), vec!["z>a:", "b>c:y", "b>c:x", "c>a:", "a>b:"]);
+
+    assert!(g.compact_if_sparse(1.1));
+    assert_eq!(names(&g), vec!["z>a:", "b>c:y", "b>c:x", "c>a:", "a>b:"]);
+}
+
+#[test]
+fn shrink_to_fit_keeps_graph_contents_and_indices() {
+    let mut g: Graph<i32, i32, ()> = Graph::with_capacity(
+        GraphOptions {
+            compound: true,
+            ..Default::default()
+        },
+        64,
+        64,
+    );
+    g.set_node("a", 1);
+    g.set_node("b", 2);
+    g.set_node("c", 3);
+    g.set_parent("b", "a");
+    g.set_edge_with_label("b", "c", 10);
+    g.set_edge_with_label("c", "a", 20);
+    g.remove_node("c");
+    let b_ix = g.node_ix("b");
+
+    g.shrink_to_fit();
+
+    assert_eq!(g.node_ix("b"), b_ix);
+    assert_eq!(g.node_ids(), vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(g.node("b"), Some(&2));
+    assert_eq!(g.parent("b"), Some("a"));
+    assert_eq!(g.edge_count(), 0);
+
+    g.set_edge_with_label("a", "b", 30);
+    assert_eq!(g.edge("a", "b", None), Some(&30));
+    assert_eq!(g.successors("a"), vec!["b"]);
+}
+
+#[test]
+fn set_node_default_applies_default_label_only_to_new_nodes() {
+    let mut g: Graph<String, (), ()> = Graph::new(GraphOptions::default());
+    g.set_default_node_label_with_id(|id| format!("default-{id}"));
+    g.set_node("a", "explicit".to_string());
+
+    g.set_node_default("a").set_node_default("b");
+
+    assert_eq!(g.node("a").map(String::as_str), Some("explicit"));
+    assert_eq!(g.node("b").map(String::as_str), Some("default-b"));
+    assert_eq!(g.node_count(), 2);
+}
+
+#[test]
+fn rename_node_rewrites_incident_edges_and_multi_edges() {
+    let mut g: Graph<i32, i32, ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_node("a", 1);
+    g.set_edge_with_label("a", "b", 10);
+    g.set_edge_named("a", "b", Some("x"), Some(11));
+    g.set_edge_with_label("c", "a", 20);
+    g.set_edge_with_label("a", "a", 30);
+
+    g.rename_node("a", "z").unwrap();
+
+    assert!(!g.has_node("a"));
+    assert_eq!(g.node("z"), Some(&1));
+    assert_eq!(g.edge("z", "b", None), Some(&10));
+    assert_eq!(g.edge("z", "b", Some("x")), Some(&11));
+    assert_eq!(g.edge("c", "z", None), Some(&20));
+    assert_eq!(g.edge("z", "z", None), Some(&30));
+    assert!(!g.has_edge("a", "b", None));
+    assert_eq!(
+        sorted_edge_tuples(g.edge_keys()),
+        vec![
+            ("c".to_string(), "z".to_string(), None),
+            ("z".to_string(), "b".to_string(), None),
+            ("z".to_string(), "b".to_string(), Some("x".to_string())),
+            ("z".to_string(), "z".to_string(), None),
+        ]
+    );
+    assert_eq!(g.out_edges("z", Some("b")).len(), 2);
+    assert_eq!(sorted(g.predecessors("z")), vec!["c", "z"]);
+    assert_eq!(g.successors("c"), vec!["z"]);
+}
+
+#[test]
+fn rename_node_keeps_compound_parent_and_children() {
+    let mut g: Graph<i32, (), ()> = Graph::new(GraphOptions {
+        compound: true,
+        ..Default::default()
+    });
+    g.set_parent("child", "mid");
+    g.set_parent("mid", "root");
+
+    g.rename_node("mid", "group").unwrap();
+
+    assert_eq!(g.parent("child"), Some("group"));
+    assert_eq!(g.parent("group"), Some("root"));
+    assert_eq!(g.children("group"), vec!["child"]);
+    assert_eq!(g.children("root"), vec!["group"]);
+    assert!(g.children_opt("mid").is_none());
+}
+
+#[test]
+fn rename_node_recanonicalizes_undirected_edges() {
+    let mut g: Graph<(), i32, ()> = Graph::new(GraphOptions {
+        directed: false,
+        ..Default::default()
+    });
+    g.set_edge_with_label("a", "m", 1);
+
+    g.rename_node("a", "z").unwrap();
+
+    assert_eq!(
+        sorted_edge_tuples(g.edge_keys()),
+        vec![("m".to_string(), "z".to_string(), None)]
+    );
+    assert_eq!(g.edge("z", "m", None), Some(&1));
+    assert_eq!(g.neighbors("m"), vec!["z"]);
+}
+
+#[test]
+fn rename_node_rejects_missing_or_taken_ids() {
+    let mut g: Graph<i32, (), ()> = Graph::new(GraphOptions::default());
+    g.set_node("a", 1);
+    g.set_node("b", 2);
+
+    assert_eq!(
+        g.rename_node("missing", "c"),
+        Err(RenameError::MissingNode("missing".to_string()))
+    );
+    assert_eq!(
+        g.rename_node("a", "b"),
+        Err(RenameError::NodeExists("b".to_string()))
+    );
+    assert_eq!(g.node("a"), Some(&1));
+    assert_eq!(g.node("b"), Some(&2));
+    assert_eq!(g.rename_node("a", "a"), Ok(()));
+}
+
+#[test]
+fn borrowing_id_and_key_iterators_match_owned_snapshots() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge("c", "a");
+    g.set_edge_named("a", "b", Some("x"), None);
+    g.set_edge("b", "c");
+    g.remove_node("a");
+    g.set_node("a", ());
+    g.set_edge("a", "b");
+
+    let ids: Vec<&str> = g.node_ids_ref().collect();
+    assert_eq!(ids, g.node_ids());
+    assert_eq!(ids, vec!["c", "b", "a"]);
+
+    let keys: Vec<&EdgeKey> = g.edge_keys_ref().collect();
+    let owned = g.edge_keys();
+    assert_eq!(keys, owned.iter().collect::<Vec<_>>());
+    assert_eq!(
+        keys,
+        vec![
+            &EdgeKey::new("b", "c", None::<&str>),
+            &EdgeKey::new("a", "b", None::<&str>)
+        ]
+    );
+}
+
+#[test]
+fn to_undirected_merges_opposite_edges_with_combined_label() {
+    let mut g: Graph<(), i32, &str> = Graph::new(GraphOptions {
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph("label");
+    g.set_edge_with_label("a", "b", 1);
+    g.set_edge_with_label("b", "a", 2);
+    g.set_edge_with_label("c", "b", 4);
+    g.set_parent("c", "p");
+
+    let u = g.to_undirected(|x, y| x + y);
+    assert!(!u.is_directed());
+    assert!(u.is_compound());
+    assert_eq!(*u.graph(), "label");
+    assert_eq!(u.edge_count(), 2);
+    assert_eq!(u.edge("a", "b", None), Some(&3));
+    assert_eq!(u.edge("b", "a", None), Some(&3));
+    assert_eq!(u.edge("b", "c", None), Some(&4));
+    assert_eq!(u.parent("c"), Some("p"));
+    assert_eq!(u.node_count(), g.node_count());
+    assert!(g.is_directed());
+    assert_eq!(g.edge_count(), 3);
+}
+
+#[test]
+fn validate_accepts_graphs_after_structural_mutations() {
+    let mut g: Graph<(), (), ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    assert_eq!(g.validate(), Ok(()));
+
+    g.set_path(&["a", "b", "c", "d"]);
+    g.set_edge_named("a", "b", Some("x"), None);
+    g.set_parent("b", "sg");
+    g.set_parent("c", "sg");
+    g.set_parent("sg", "root");
+    assert_eq!(g.validate(), Ok(()));
+
+    g.set_parent("c", "root");
+    assert!(g.remove_node("sg"));
+    assert!(g.remove_edge("c", "d", None));
+    g.rename_node("a", "z").unwrap();
+    assert_eq!(g.validate(), Ok(()));
+
+    for v in ["b", "z"] {
+        assert!(g.remove_node(v));
+    }
+    assert!(g.compact_if_sparse(1.5));
+    assert_eq!(g.validate(), Ok(()));
+    assert_eq!(g.parent("c"), Some("root"));
+
+    let mut u: Graph<(), (), ()> = Graph::new(GraphOptions {
+        directed: false,
+        ..Default::default()
+    });
+    u.set_edge("b", "a");
+    u.rename_node("a", "c").unwrap();
+    assert_eq!(u.validate(), Ok(()));
+}
@@ -15,6 +15,24 @@ fn undirected_edges_are_symmetric() {
     assert_eq!(g.edge("b", "a", None), Some(&7));
 }
 
+#[test]
+fn undirected_adjacency_matrix_is_symmetric() {
+    let mut g: Graph<(), f64, ()> = Graph::new(GraphOptions {
+        directed: false,
+        ..Default::default()
+    });
+    g.set_edge_with_label("b", "a", 2.0);
+    g.set_edge_with_label("a", "a", 1.0);
+
+    let (ids, matrix) = g.to_adjacency_matrix(|_, w| *w);
+
+    assert_eq!(ids, vec!["a", "b"]);
+    assert_eq!(
+        matrix,
+        vec![vec![Some(1.0), Some(2.0)], vec![Some(2.0), None]]
+    );
+}
+
 #[test]
 fn undirected_edges_are_incident_for_in_and_out_edges() {
     let mut g: Graph<(), (), ()> = Graph::new(GraphOptions {
@@ -74,14 +74,18 @@ where
     let mut buckets: Vec<VecDeque<String>> = (0..bucket_len).map(|_| VecDeque::new()).collect();
     let mut bucket_of: HashMap<String, usize> = HashMap::default();
 
-    for v in &node_ids {
-        assign_bucket(v, &in_w, &out_w, &mut buckets, zero_idx, &mut bucket_of);
-    }
-
     // Build adjacency for the aggregated graph (for efficient updates).
+    //
+    // Sinks and sources are classified by their remaining alive edges rather than by weight:
+    // with zero or negative weights a node can have `out == 0` while still sitting on a cycle,
+    // and dropping it as a sink would lose the edges that need to be reversed.
     let mut in_edges: HashMap<String, Vec<(String, i64)>> = HashMap::default();
     let mut out_edges: HashMap<String, Vec<(String, i64)>> = HashMap::default();
+    let mut in_deg: HashMap<String, usize> = HashMap::default();
+    let mut out_deg: HashMap<String, usize> = HashMap::default();
     for (v, w) in &edge_order {
+        *out_deg.entry(v.clone()).or_insert(0) += 1;
+        *in_deg.entry(w.clone()).or_insert(0) += 1;
         let wgt = edge_w.get(&(v.clone(), w.clone())).copied().unwrap_or(0);
         out_edges
             .entry(v.clone())
@@ -103,11 +107,38 @@ where
         bucket_of: &'a mut HashMap<String, usize>,
         in_w: &'a mut HashMap<String, i64>,
         out_w: &'a mut HashMap<String, i64>,
+        in_deg: &'a mut HashMap<String, usize>,
+        out_deg: &'a mut HashMap<String, usize>,
         in_edges: &'a HashMap<String, Vec<(String, i64)>>,
         out_edges: &'a HashMap<String, Vec<(String, i64)>>,
     }
 
     impl Work<'_> {
+        fn assign_bucket(&mut self, v: &str) {
+            if let Some(prev) = self.bucket_of.get(v).copied()
+                && let Some(pos) = self.buckets[prev].iter().position(|x| x == v)
+            {
+                self.buckets[prev].remove(pos);
+            }
+
+            let last = self.buckets.len() - 1;
+            let idx: usize = if self.out_deg.get(v).copied().unwrap_or(0) == 0 {
+                0
+            } else if self.in_deg.get(v).copied().unwrap_or(0) == 0 {
+                last
+            } else {
+                // Keep nodes with alive edges on both sides out of the sink/source buckets even
+                // when negative aggregate weights push the delta past either end.
+                let in_v = self.in_w.get(v).copied().unwrap_or(0);
+                let out_v = self.out_w.get(v).copied().unwrap_or(0);
+                let raw = out_v.saturating_sub(in_v).saturating_add(self.zero_idx);
+                raw.clamp(1, (last - 1) as i64) as usize
+            };
+
+            self.buckets[idx].push_front(v.to_string());
+            self.bucket_of.insert(v.to_string(), idx);
+        }
+
         fn pop_bucket(&mut self, idx: usize) -> Option<String> {
             pop_bucket(&mut self.buckets[idx], &*self.alive)
         }
@@ -147,14 +178,10 @@ where
                     if let Some(o) = self.out_w.get_mut(u) {
                         *o -= *wgt;
                     }
-                    assign_bucket(
-                        u,
-                        &*self.in_w,
-                        &*self.out_w,
-                        self.buckets,
-                        self.zero_idx,
-                        self.bucket_of,
-                    );
+                    if let Some(d) = self.out_deg.get_mut(u) {
+                        *d -= 1;
+                    }
+                    self.assign_bucket(u);
                 }
             }
 
@@ -166,14 +193,10 @@ where
                     if let Some(i) = self.in_w.get_mut(w) {
                         *i -= *wgt;
                     }
-                    assign_bucket(
-                        w,
-                        &*self.in_w,
-                        &*self.out_w,
-                        self.buckets,
-                        self.zero_idx,
-                        self.bucket_of,
-                    );
+                    if let Some(d) = self.in_deg.get_mut(w) {
+                        *d -= 1;
+                    }
+                    self.assign_bucket(w);
                 }
             }
 
@@ -190,10 +213,16 @@ where
         bucket_of: &mut bucket_of,
         in_w: &mut in_w,
         out_w: &mut out_w,
+        in_deg: &mut in_deg,
+        out_deg: &mut out_deg,
         in_edges: &in_edges,
         out_edges: &out_edges,
     };
 
+    for v in &node_ids {
+        work.assign_bucket(v);
+    }
+
     while !work.alive.is_empty() {
         // Drain sinks (out == 0).
         while let Some(v) = work.pop_bucket(0) {
@@ -254,32 +283,3 @@ fn pop_bucket(bucket: &mut VecDeque<String>, alive: &HashSet<String>) -> Option<
     }
     None
 }
-
-fn assign_bucket(
-    v: &str,
-    in_w: &HashMap<String, i64>,
-    out_w: &HashMap<String, i64>,
-    buckets: &mut [VecDeque<String>],
-    zero_idx: i64,
-    bucket_of: &mut HashMap<String, usize>,
-) {
-    if let Some(prev) = bucket_of.get(v).copied()
-        && let Some(pos) = buckets[prev].iter().position(|x| x == v)
-    {
-        buckets[prev].remove(pos);
-    }
-
-    let in_v = in_w.get(v).copied().unwrap_or(0);
-    let out_v = out_w.get(v).copied().unwrap_or(0);
-    let idx: usize = if out_v == 0 {
-        0
-    } else if in_v == 0 {
-        buckets.len() - 1
-    } else {
-        let raw = out_v - in_v + zero_idx;
-        raw.clamp(0, (buckets.len() - 1) as i64) as usize
-    };
-
-    buckets[idx].push_front(v.to_string());
-    bucket_of.insert(v.to_string(), idx);
-}
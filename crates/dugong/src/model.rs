@@ -23,11 +23,26 @@ pub struct GraphLabel {
     pub width: f64,
     pub height: f64,
     pub align: Option<String>,
+    /// Ranking algorithm used by [`crate::rank::rank`]: `"network-simplex"` (the default, also
+    /// used for unknown values), `"tight-tree"`, `"longest-path"`, or `"none"`. With `"none"` the
+    /// ranker is skipped and the caller-provided `NodeLabel.rank` values are kept as-is.
     pub ranker: Option<String>,
     pub acyclicer: Option<String>,
     pub dummy_chains: Vec<String>,
     pub nesting_root: Option<String>,
     pub node_rank_factor: Option<usize>,
+    /// Upper bound on the gap between consecutive occupied ranks. After ranking, `layout_dagreish`
+    /// compresses longer runs of empty ranks down to this length (see
+    /// [`crate::util::clamp_rank_gaps`]), so a pathological `minlen` cannot blow up the dummy
+    /// chains and per-rank layers built by `normalize` and `order`. Values below 2 are treated as
+    /// 2 so edge-label ranks keep a slot between their endpoints. `None` means unlimited.
+    pub max_edge_length: Option<usize>,
+    /// Set when [`Self::max_edge_length`] compressed at least one rank gap during layout.
+    pub edge_length_clamped: bool,
+    /// Set when network simplex ranking stopped at its pivot cap (see
+    /// [`crate::rank::network_simplex::MAX_PIVOTS_PER_EDGE`]) and kept a feasible but possibly
+    /// non-optimal ranking.
+    pub rank_pivots_capped: bool,
 }
 
 impl Default for GraphLabel {
@@ -47,6 +62,9 @@ impl Default for GraphLabel {
             dummy_chains: Vec::new(),
             nesting_root: None,
             node_rank_factor: None,
+            max_edge_length: None,
+            edge_length_clamped: false,
+            rank_pivots_capped: false,
         }
     }
 }
@@ -2,7 +2,7 @@
 //!
 //! Ported from Dagre's `barycenter`, `resolveConflicts`, and `sortSubgraph` helpers.
 
-use super::{OrderEdgeWeight, OrderNodeLabel};
+use super::{Heuristic, OrderEdgeWeight, OrderNodeLabel};
 use crate::graphlib::Graph;
 use rustc_hash::FxHashMap as HashMap;
 use web_time::{Duration, Instant};
@@ -566,6 +566,7 @@ pub(crate) fn sort_subgraph_ix<N, E, G, CN, CE, CG>(
     v: &str,
     cg: &Graph<CN, CE, CG>,
     bias_right: bool,
+    heuristic: Heuristic,
 ) -> SortResultIx
 where
     N: Default + OrderNodeLabel + Clone + 'static,
@@ -575,7 +576,7 @@ where
     CE: Default + 'static,
     CG: Default,
 {
-    sort_subgraph_ix_iterative(g, v, cg, bias_right, None, false)
+    sort_subgraph_ix_iterative(g, v, cg, bias_right, heuristic, None, false)
 }
 
 pub(crate) fn sort_subgraph_with_timings_ix<N, E, G, CN, CE, CG>(
@@ -583,6 +584,7 @@ pub(crate) fn sort_subgraph_with_timings_ix<N, E, G, CN, CE, CG>(
     v: &str,
     cg: &Graph<CN, CE, CG>,
     bias_right: bool,
+    heuristic: Heuristic,
     timings: &mut SortSubgraphTimings,
 ) -> SortResultIx
 where
@@ -593,7 +595,7 @@ where
     CE: Default + 'static,
     CG: Default,
 {
-    sort_subgraph_ix_iterative(g, v, cg, bias_right, Some(timings), true)
+    sort_subgraph_ix_iterative(g, v, cg, bias_right, heuristic, Some(timings), true)
 }
 
 fn sort_subgraph_ix_iterative<N, E, G, CN, CE, CG>(
@@ -601,6 +603,7 @@ fn sort_subgraph_ix_iterative<N, E, G, CN, CE, CG>(
     v: &str,
     cg: &Graph<CN, CE, CG>,
     bias_right: bool,
+    heuristic: Heuristic,
     mut timings: Option<&mut SortSubgraphTimings>,
     sort_movable_by_order: bool,
 ) -> SortResultIx
@@ -670,7 +673,10 @@ where
                 }
 
                 let barycenter_start = timings.is_some().then(Instant::now);
-                let barycenters = barycenter_ix(g, &movable);
+                let barycenters = match heuristic {
+                    Heuristic::Barycenter => barycenter_ix(g, &movable),
+                    Heuristic::Median => median_ix(g, &movable),
+                };
                 if let (Some(start), Some(t)) = (barycenter_start, timings.as_deref_mut()) {
                     t.barycenter += start.elapsed();
                 }
@@ -887,6 +893,65 @@ where
         .collect()
 }
 
+/// Like [`barycenter_ix`], but the sort key is the weighted median of the in-neighbor orders.
+///
+/// When the cumulative weight hits exactly half at a neighbor, the key is the midpoint between it
+/// and the next one (the classic even-count median). The entry weight stays the total edge
+/// weight so subgraph merging and conflict resolution treat both heuristics alike.
+fn median_ix<N, E, G>(g: &Graph<N, E, G>, movable_ix: &[usize]) -> Vec<BarycenterEntryIx>
+where
+    N: Default + OrderNodeLabel + 'static,
+    E: Default + OrderEdgeWeight + 'static,
+    G: Default,
+{
+    movable_ix
+        .iter()
+        .map(|&v_ix| {
+            let mut neighbors: Vec<(f64, f64)> = Vec::new();
+            g.for_each_in_edge_ix(v_ix, None, |u_ix, _w_ix, _ek, lbl| {
+                let u_order = g
+                    .node_label_by_ix(u_ix)
+                    .and_then(|n| n.order())
+                    .map(|n| n as f64)
+                    .unwrap_or(0.0);
+                neighbors.push((u_order, lbl.weight()));
+            });
+
+            if neighbors.is_empty() {
+                return BarycenterEntryIx {
+                    v_ix,
+                    barycenter: None,
+                    weight: None,
+                };
+            }
+
+            neighbors.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let weight: f64 = neighbors.iter().map(|(_, w)| w).sum();
+            let half = weight / 2.0;
+            let mut cumulative = 0.0;
+            let mut median = neighbors[neighbors.len() - 1].0;
+            for (i, &(order, w)) in neighbors.iter().enumerate() {
+                cumulative += w;
+                if cumulative > half {
+                    median = order;
+                    break;
+                }
+                if cumulative == half {
+                    let next = neighbors.get(i + 1).map_or(order, |n| n.0);
+                    median = (order + next) / 2.0;
+                    break;
+                }
+            }
+
+            BarycenterEntryIx {
+                v_ix,
+                barycenter: Some(median),
+                weight: Some(weight),
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 struct ConflictEntryIx {
     indegree: usize,
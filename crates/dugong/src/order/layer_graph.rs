@@ -36,11 +36,7 @@ where
     E: Default + 'static + OrderEdgeWeight,
     G: Default,
 {
-    let mut result: Graph<N, WeightLabel, LayerGraphLabel> = Graph::new(GraphOptions {
-        compound: true,
-        multigraph: false,
-        ..Default::default()
-    });
+    let mut result: Graph<N, WeightLabel, LayerGraphLabel> = Graph::new(GraphOptions::compound());
     result.set_graph(LayerGraphLabel {
         root: root.to_string(),
     });
@@ -143,11 +139,7 @@ where
 {
     let root_id = root.to_string();
     let mut result: Graph<OrderNodeLite, WeightLabel, LayerGraphLabel> = Graph::with_capacity(
-        GraphOptions {
-            compound: true,
-            multigraph: false,
-            ..Default::default()
-        },
+        GraphOptions::compound(),
         // Root + at least current-rank nodes; edges can add adjacent nodes.
         (nodes_with_rank.len() + 1).saturating_mul(2),
         // Edges in the layer graph are limited to incident edges of current-rank nodes. Avoid
@@ -27,4 +27,4 @@ mod cross_count;
 pub use cross_count::cross_count;
 
 mod ordering;
-pub use ordering::{OrderOptions, order};
+pub use ordering::{Heuristic, OrderOptions, OrderReport, order, order_with_report};
@@ -8,9 +8,30 @@ use super::{
 };
 use crate::graphlib::{Graph, GraphOptions};
 
+/// The per-layer sort key used by each ordering sweep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    /// Dagre's weighted mean of the neighbor positions.
+    #[default]
+    Barycenter,
+    /// Weighted median of the neighbor positions (Gansner et al.); less sensitive to a single
+    /// far-away neighbor.
+    Median,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct OrderOptions {
     pub disable_optimal_order_heuristic: bool,
+    pub heuristic: Heuristic,
+}
+
+/// Outcome of [`order_with_report`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OrderReport {
+    /// `cross_count` of the layering that was assigned.
+    pub crossings: f64,
+    /// Number of sweeps run; `0` when the optimal-order heuristic is disabled.
+    pub iterations: usize,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -39,6 +60,28 @@ struct OrderTimings {
 }
 
 pub fn order<N, E, G>(g: &mut Graph<N, E, G>, opts: OrderOptions)
+where
+    N: Default + Clone + OrderNodeLabel + 'static,
+    E: Default + OrderEdgeWeight + 'static,
+    G: Default,
+{
+    let _ = order_impl(g, opts, false);
+}
+
+/// Same as [`order`], additionally reporting the crossing count of the chosen layering and
+/// how many sweeps it took to find it.
+pub fn order_with_report<N, E, G>(g: &mut Graph<N, E, G>, opts: OrderOptions) -> OrderReport
+where
+    N: Default + Clone + OrderNodeLabel + 'static,
+    E: Default + OrderEdgeWeight + 'static,
+    G: Default,
+{
+    order_impl(g, opts, true)
+}
+
+/// `want_report` only matters when the heuristic is disabled: the sweeps count crossings anyway,
+/// but the initial layering is otherwise never scored.
+fn order_impl<N, E, G>(g: &mut Graph<N, E, G>, opts: OrderOptions, want_report: bool) -> OrderReport
 where
     N: Default + Clone + OrderNodeLabel + 'static,
     E: Default + OrderEdgeWeight + 'static,
@@ -85,7 +128,7 @@ where
     }
 
     if max_rank == i32::MIN {
-        return;
+        return OrderReport::default();
     }
 
     let init_order_start = timing_enabled.then(web_time::Instant::now);
@@ -101,7 +144,14 @@ where
     }
 
     if opts.disable_optimal_order_heuristic {
-        return;
+        if !want_report {
+            return OrderReport::default();
+        }
+        let layering = build_layer_matrix_ix(g, max_rank);
+        return OrderReport {
+            crossings: cross_count_ix(g, &layering),
+            iterations: 0,
+        };
     }
 
     let root = create_root_node(g);
@@ -157,6 +207,7 @@ where
                 g,
                 &ranks_down,
                 bias_right,
+                opts.heuristic,
                 &root,
                 &mut layer_graphs_in,
                 timing_enabled,
@@ -171,6 +222,7 @@ where
                 g,
                 &ranks_up,
                 bias_right,
+                opts.heuristic,
                 &root,
                 &mut layer_graphs_out,
                 timing_enabled,
@@ -232,6 +284,11 @@ where
             timings.sort_subgraph_border_adjust,
         );
     }
+
+    OrderReport {
+        crossings: best_cc,
+        iterations: i,
+    }
 }
 
 fn assign_order<N, E, G>(g: &mut Graph<N, E, G>, layering: &[Vec<String>])
@@ -249,10 +306,12 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn sweep<N, E, G>(
     g: &mut Graph<N, E, G>,
     ranks: &[i32],
     bias_right: bool,
+    heuristic: Heuristic,
     root: &str,
     layer_graphs: &mut [Graph<OrderNodeLite, WeightLabel, LayerGraphLabel>],
     timing_enabled: bool,
@@ -282,9 +341,11 @@ fn sweep<N, E, G>(
         let sort_start = timing_enabled.then(web_time::Instant::now);
         let mut sg_timings = timing_enabled.then(SortSubgraphTimings::default);
         let sorted = if let Some(t) = sg_timings.as_mut() {
-            super::barycenter::sort_subgraph_with_timings_ix(lg, root, &cg, bias_right, t)
+            super::barycenter::sort_subgraph_with_timings_ix(
+                lg, root, &cg, bias_right, heuristic, t,
+            )
         } else {
-            super::barycenter::sort_subgraph_ix(lg, root, &cg, bias_right)
+            super::barycenter::sort_subgraph_ix(lg, root, &cg, bias_right, heuristic)
         };
         if let Some(s) = sort_start {
             timings.sweep_sort_subgraph += s.elapsed();
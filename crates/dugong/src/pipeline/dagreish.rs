@@ -138,6 +138,9 @@ pub fn layout_dagreish(g: &mut graphlib::Graph<NodeLabel, EdgeLabel, GraphLabel>
     } else {
         let mut rank_graph = util::as_non_compound_graph(g);
         rank::rank(&mut rank_graph);
+        if rank_graph.graph().rank_pivots_capped {
+            g.graph_mut().rank_pivots_capped = true;
+        }
         // Mirror Dagre's JS behavior: `rank(asNonCompoundGraph(g))` mutates the same label objects
         // for leaf nodes, but does not propagate ranks to compound nodes (nodes with children).
         //
@@ -155,6 +158,7 @@ pub fn layout_dagreish(g: &mut graphlib::Graph<NodeLabel, EdgeLabel, GraphLabel>
             }
         }
     }
+    util::clamp_rank_gaps(g);
     if let Some(s) = rank_start {
         timings.rank = s.elapsed();
     }
@@ -314,6 +318,7 @@ pub fn layout_dagreish(g: &mut graphlib::Graph<NodeLabel, EdgeLabel, GraphLabel>
             g,
             order::OrderOptions {
                 disable_optimal_order_heuristic: false,
+                ..Default::default()
             },
         );
     }
@@ -563,9 +568,10 @@ pub fn layout_dagreish(g: &mut graphlib::Graph<NodeLabel, EdgeLabel, GraphLabel>
     if let Some(s) = total_start {
         timings.total = s.elapsed();
         eprintln!(
-            "[dugong-timing] pipeline=dagreish nodes={} edges={} total={:?} preprocess={:?} self_edges_remove={:?} acyclic={:?} nesting_run={:?} rank={:?} edge_label_proxies={:?} assign_rank_min_max={:?} normalize_run={:?} compound_border={:?} order={:?} coord_adjust={:?} self_edges_insert={:?} layering_y={:?} position_x={:?} self_edges_position={:?} remove_border_nodes={:?} normalize_undo={:?} translate={:?} edge_points={:?} acyclic_undo={:?}",
+            "[dugong-timing] pipeline=dagreish nodes={} edges={} rank_pivots_capped={} total={:?} preprocess={:?} self_edges_remove={:?} acyclic={:?} nesting_run={:?} rank={:?} edge_label_proxies={:?} assign_rank_min_max={:?} normalize_run={:?} compound_border={:?} order={:?} coord_adjust={:?} self_edges_insert={:?} layering_y={:?} position_x={:?} self_edges_position={:?} remove_border_nodes={:?} normalize_undo={:?} translate={:?} edge_points={:?} acyclic_undo={:?}",
             g.node_count(),
             g.edge_count(),
+            g.graph().rank_pivots_capped,
             timings.total,
             timings.preprocess,
             timings.self_edges_remove,
@@ -117,9 +117,9 @@ pub fn layout(g: &mut graphlib::Graph<NodeLabel, EdgeLabel, GraphLabel>) {
         // Compact ranks inside compound nodes where a common rank is feasible, to minimize cluster height.
         // This is a small parity-oriented step to match upstream Dagre behavior for subgraphs.
         let parents: Vec<String> = g
-            .node_ids()
-            .into_iter()
+            .node_ids_ref()
             .filter(|id| !g.children(id).is_empty())
+            .map(str::to_string)
             .collect();
 
         for parent in parents {
@@ -13,9 +13,9 @@ pub fn position(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
     // Upstream dagre positions a non-compound view of the graph.
     // We mimic that by ignoring cluster nodes (nodes with children).
     let leaf_ids: Vec<String> = g
-        .node_ids()
-        .into_iter()
+        .node_ids_ref()
         .filter(|id| !g.options().compound || g.children(id).is_empty())
+        .map(str::to_string)
         .collect();
 
     let mut ranks: BTreeMap<i32, Vec<String>> = BTreeMap::new();
@@ -17,6 +17,7 @@ pub fn rank(g: &mut crate::graphlib::Graph<crate::NodeLabel, crate::EdgeLabel, c
             let _ = feasible_tree::feasible_tree(g);
         }
         Some("longest-path") => util::longest_path(g),
+        // Ranks were assigned externally; leave every `NodeLabel.rank` untouched.
         Some("none") => {}
         _ => network_simplex::network_simplex(g),
     }
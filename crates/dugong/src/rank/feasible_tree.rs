@@ -17,10 +17,8 @@ pub fn feasible_tree(
     let mut in_tree_by_ix: Vec<bool> = vec![false; rank_by_ix.len()];
     let mut tree_g_ixs: Vec<usize> = Vec::new();
 
-    let mut t: Graph<tree::TreeNodeLabel, tree::TreeEdgeLabel, ()> = Graph::new(GraphOptions {
-        directed: false,
-        ..Default::default()
-    });
+    let mut t: Graph<tree::TreeNodeLabel, tree::TreeEdgeLabel, ()> =
+        Graph::new(GraphOptions::undirected());
 
     let Some(start) = g.nodes().next().map(|s| s.to_string()) else {
         return t;
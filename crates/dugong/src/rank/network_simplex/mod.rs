@@ -527,7 +527,26 @@ impl TreeState {
     }
 }
 
+/// Pivot budget per input edge used by [`network_simplex`].
+///
+/// Real diagrams converge in far fewer pivots than they have edges; the cap only exists so a
+/// degenerate input (or a cycling bug) cannot hang layout.
+pub const MAX_PIVOTS_PER_EDGE: usize = 10;
+
 pub fn network_simplex(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
+    let max_pivots = g.edge_count().saturating_mul(MAX_PIVOTS_PER_EDGE).max(100);
+    let _ = network_simplex_with_max_pivots(g, max_pivots);
+}
+
+/// Same as [`network_simplex`], stopping after at most `max_pivots` tree-edge exchanges.
+///
+/// Returns `true` when the ranking converged (no tree edge with a negative cut value is left).
+/// When the cap is hit, the current feasible ranking is kept, `false` is returned, and
+/// [`GraphLabel::rank_pivots_capped`] is set.
+pub fn network_simplex_with_max_pivots(
+    g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>,
+    max_pivots: usize,
+) -> bool {
     let mut simplified = crate::util::simplify(g);
     util::longest_path(&mut simplified);
     let mut t = feasible_tree::feasible_tree(&mut simplified);
@@ -542,7 +561,14 @@ pub fn network_simplex(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
         rank_by_ix[g_ix] = lbl.rank.unwrap_or(0);
     });
 
+    let mut pivots = 0usize;
+    let mut converged = true;
     while let Some((leave_u_tix, leave_v_tix)) = t_state.find_leave_edge_in_insertion_order(&t) {
+        if pivots >= max_pivots {
+            converged = false;
+            break;
+        }
+        pivots += 1;
         let leave_u_id = t.node_id_by_ix(leave_u_tix);
         let leave_v_id = t.node_id_by_ix(leave_v_tix);
         let Some((leave_u_id, leave_v_id)) = leave_u_id.zip(leave_v_id) else {
@@ -572,6 +598,10 @@ pub fn network_simplex(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
             lbl.rank = Some(rank);
         }
     }
+    if !converged {
+        g.graph_mut().rank_pivots_capped = true;
+    }
+    converged
 }
 
 fn enter_edge_fast(
@@ -956,7 +986,7 @@ fn assign_cut_value(
         return;
     };
     let cutvalue = calc_cut_value(t, g, child);
-    if let Some(edge) = t.edge_mut(child, &parent, None) {
+    if let Some(edge) = t.edge_simple_mut(child, &parent) {
         edge.cutvalue = cutvalue;
     }
 }
@@ -1023,7 +1053,7 @@ pub fn calc_cut_value(
                 -lbl.weight
             };
 
-            if let Some(other_edge) = t.edge(child, other, None) {
+            if let Some(other_edge) = t.edge_simple(child, other) {
                 let other_cut_value = other_edge.cutvalue;
                 cut_value += if points_to_head {
                     -other_cut_value
@@ -1048,7 +1078,7 @@ pub fn calc_cut_value(
                 -lbl.weight
             };
 
-            if let Some(other_edge) = t.edge(child, other, None) {
+            if let Some(other_edge) = t.edge_simple(child, other) {
                 let other_cut_value = other_edge.cutvalue;
                 cut_value += if points_to_head {
                     -other_cut_value
@@ -1071,7 +1101,7 @@ pub fn calc_cut_value(
                 -lbl.weight
             };
 
-            if let Some(other_edge) = t.edge(child, other, None) {
+            if let Some(other_edge) = t.edge_simple(child, other) {
                 let other_cut_value = other_edge.cutvalue;
                 cut_value += if points_to_head {
                     -other_cut_value
@@ -1094,7 +1124,7 @@ pub fn calc_cut_value(
                 -lbl.weight
             };
 
-            if let Some(other_edge) = t.edge(child, other, None) {
+            if let Some(other_edge) = t.edge_simple(child, other) {
                 let other_cut_value = other_edge.cutvalue;
                 cut_value += if points_to_head {
                     -other_cut_value
@@ -1132,4 +1162,4 @@ pub fn exchange_edges(
 }
 
 // NOTE: Dagre treats the feasible tree as an undirected structure. We consider an edge to be a
-// tree edge if it exists in `t` (queried via `t.edge(u, v, None)` in the hot loops).
+// tree edge if it exists in `t` (queried via `t.edge_simple(u, v)` in the hot loops).
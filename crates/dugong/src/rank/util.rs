@@ -2,12 +2,11 @@
 
 use crate::graphlib::{EdgeKey, Graph};
 use crate::{EdgeLabel, GraphLabel, NodeLabel};
-use rustc_hash::FxHashMap as HashMap;
 
 pub fn longest_path(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
     struct Frame {
-        v: String,
-        edges: Vec<EdgeKey>,
+        v_ix: usize,
+        edges: Vec<(usize, i32)>,
         next_edge: usize,
         rank: Option<i32>,
         incoming_minlen: Option<i32>,
@@ -20,63 +19,101 @@ pub fn longest_path(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
         });
     }
 
-    let sources: Vec<String> = g.sources().into_iter().map(|s| s.to_string()).collect();
-    let mut visited: HashMap<String, i32> = HashMap::default();
-    for v in sources {
-        if visited.contains_key(&v) {
-            continue;
-        }
-
-        let mut stack = vec![Frame {
-            edges: g.out_edges(&v, None),
-            v,
-            next_edge: 0,
-            rank: None,
-            incoming_minlen: None,
-        }];
+    fn out_edges(g: &Graph<NodeLabel, EdgeLabel, GraphLabel>, v_ix: usize) -> Vec<(usize, i32)> {
+        let mut edges = Vec::new();
+        g.for_each_out_edge_ix(v_ix, None, |_v_ix, w_ix, _key, lbl| {
+            edges.push((w_ix, lbl.minlen as i32));
+        });
+        edges
+    }
 
-        while let Some(frame) = stack.last_mut() {
-            if let Some(rank) = visited.get(frame.v.as_str()).copied() {
-                let incoming_minlen = frame.incoming_minlen;
-                let _ = stack.pop();
-                if let (Some(parent), Some(minlen)) = (stack.last_mut(), incoming_minlen) {
-                    apply_candidate(&mut parent.rank, rank - minlen);
-                }
+    fn walk(
+        g: &Graph<NodeLabel, EdgeLabel, GraphLabel>,
+        roots: &[usize],
+        rank_by_ix: &mut [Option<i32>],
+        on_stack: &mut [bool],
+    ) -> usize {
+        let mut ranked = 0;
+        for &root in roots {
+            if rank_by_ix[root].is_some() {
                 continue;
             }
 
-            if frame.next_edge < frame.edges.len() {
-                let edge = frame.edges[frame.next_edge].clone();
-                frame.next_edge += 1;
-                let minlen = g
-                    .edge_by_key(&edge)
-                    .map(|lbl| lbl.minlen as i32)
-                    .unwrap_or(1);
-                if let Some(child_rank) = visited.get(edge.w.as_str()).copied() {
-                    apply_candidate(&mut frame.rank, child_rank - minlen);
-                } else {
-                    stack.push(Frame {
-                        edges: g.out_edges(&edge.w, None),
-                        v: edge.w,
-                        next_edge: 0,
-                        rank: None,
-                        incoming_minlen: Some(minlen),
-                    });
+            on_stack[root] = true;
+            let mut stack = vec![Frame {
+                v_ix: root,
+                edges: out_edges(g, root),
+                next_edge: 0,
+                rank: None,
+                incoming_minlen: None,
+            }];
+
+            while let Some(frame) = stack.last_mut() {
+                if frame.next_edge < frame.edges.len() {
+                    let (w_ix, minlen) = frame.edges[frame.next_edge];
+                    frame.next_edge += 1;
+                    if let Some(child_rank) = rank_by_ix[w_ix] {
+                        apply_candidate(&mut frame.rank, child_rank - minlen);
+                    } else if !on_stack[w_ix] {
+                        on_stack[w_ix] = true;
+                        stack.push(Frame {
+                            v_ix: w_ix,
+                            edges: out_edges(g, w_ix),
+                            next_edge: 0,
+                            rank: None,
+                            incoming_minlen: Some(minlen),
+                        });
+                    }
+                    continue;
                 }
-                continue;
-            }
 
-            let Some(frame) = stack.pop() else {
-                break;
-            };
-            let rank = frame.rank.unwrap_or(0);
-            if let Some(label) = g.node_mut(&frame.v) {
-                label.rank = Some(rank);
+                let Some(frame) = stack.pop() else {
+                    break;
+                };
+                let rank = frame.rank.unwrap_or(0);
+                on_stack[frame.v_ix] = false;
+                rank_by_ix[frame.v_ix] = Some(rank);
+                ranked += 1;
+                if let (Some(parent), Some(minlen)) = (stack.last_mut(), frame.incoming_minlen) {
+                    apply_candidate(&mut parent.rank, rank - minlen);
+                }
             }
-            visited.insert(frame.v, rank);
-            if let (Some(parent), Some(minlen)) = (stack.last_mut(), frame.incoming_minlen) {
-                apply_candidate(&mut parent.rank, rank - minlen);
+        }
+        ranked
+    }
+
+    let mut ix_len: usize = 0;
+    g.for_each_node_ix(|ix, _id, _lbl| {
+        ix_len = ix_len.max(ix + 1);
+    });
+    let mut rank_by_ix: Vec<Option<i32>> = vec![None; ix_len];
+    let mut on_stack: Vec<bool> = vec![false; ix_len];
+
+    let sources: Vec<usize> = g
+        .sources()
+        .into_iter()
+        .filter_map(|v| g.node_ix(v))
+        .collect();
+    let mut ranked = walk(g, &sources, &mut rank_by_ix, &mut on_stack);
+
+    // Dagre only walks from sources and assumes `acyclic` already ran. To stay terminating when
+    // called directly on a cyclic graph, edges back into the current DFS path are ignored, and
+    // nodes left unranked (cycles with no source) are walked afterwards in insertion order.
+    // Both are no-ops for DAGs.
+    if ranked < g.node_count() {
+        let mut remaining: Vec<usize> = Vec::new();
+        g.for_each_node_ix(|ix, _id, _lbl| {
+            if rank_by_ix[ix].is_none() {
+                remaining.push(ix);
             }
+        });
+        ranked += walk(g, &remaining, &mut rank_by_ix, &mut on_stack);
+        debug_assert_eq!(ranked, g.node_count());
+    }
+
+    for (ix, rank) in rank_by_ix.into_iter().enumerate() {
+        if let (Some(rank), Some(label)) = (rank, g.node_label_mut_by_ix(ix)) {
+            label.rank = Some(rank);
         }
     }
 }
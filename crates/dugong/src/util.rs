@@ -23,11 +23,7 @@ where
     N: Default + Clone + 'static,
     G: Default + Clone,
 {
-    let mut simplified: Graph<N, EdgeLabel, G> = Graph::new(GraphOptions {
-        multigraph: false,
-        compound: false,
-        ..Default::default()
-    });
+    let mut simplified: Graph<N, EdgeLabel, G> = Graph::new(GraphOptions::directed_simple());
     simplified.set_graph(g.graph().clone());
 
     for v in g.node_ids() {
@@ -77,18 +73,18 @@ where
     E: Default + Clone + 'static,
     G: Default + Clone,
 {
-    let mut simplified: Graph<N, E, G> = Graph::new(GraphOptions {
-        multigraph: g.options().multigraph,
-        compound: false,
-        ..Default::default()
-    });
+    let mut simplified: Graph<N, E, G> = Graph::new(
+        GraphOptions::builder()
+            .multigraph(g.options().multigraph)
+            .build(),
+    );
     simplified.set_graph(g.graph().clone());
 
-    for v in g.node_ids() {
-        if g.children(&v).is_empty()
-            && let Some(lbl) = g.node(&v)
+    for v in g.node_ids_ref() {
+        if g.children(v).is_empty()
+            && let Some(lbl) = g.node(v)
         {
-            simplified.set_node(v, lbl.clone());
+            simplified.set_node(v.to_string(), lbl.clone());
         }
     }
 
@@ -139,6 +135,33 @@ where
     out
 }
 
+/// Sums `weight_fn` over the out-edges of `v` without building the nested map produced by
+/// [`successor_weights`]. Returns `0.0` for unknown nodes.
+pub fn total_successor_weight<N, E, G, F>(g: &Graph<N, E, G>, v: &str, mut weight_fn: F) -> f64
+where
+    N: Default + 'static,
+    E: Default + 'static,
+    G: Default,
+    F: FnMut(&E) -> f64,
+{
+    let mut total = 0.0;
+    g.for_each_out_edge(v, None, |_, label| total += weight_fn(label));
+    total
+}
+
+/// Predecessor counterpart of [`total_successor_weight`].
+pub fn total_predecessor_weight<N, E, G, F>(g: &Graph<N, E, G>, v: &str, mut weight_fn: F) -> f64
+where
+    N: Default + 'static,
+    E: Default + 'static,
+    G: Default,
+    F: FnMut(&E) -> f64,
+{
+    let mut total = 0.0;
+    g.for_each_in_edge(v, None, |_, label| total += weight_fn(label));
+    total
+}
+
 pub fn intersect_rect(rect: Rect, point: Point) -> Point {
     let x = rect.x;
     let y = rect.y;
@@ -231,6 +254,8 @@ pub fn time<T>(name: &str, f: impl FnOnce() -> T) -> T {
     time_to_writer(name, &mut stdout, f)
 }
 
+/// Shifts every ranked node so the minimum rank becomes 0 (network simplex can leave negative
+/// ranks). Nodes without a rank, such as subgraphs, are left untouched.
 pub fn normalize_ranks<E, G>(g: &mut Graph<NodeLabel, E, G>)
 where
     E: Default + 'static,
@@ -252,6 +277,51 @@ where
     });
 }
 
+/// Shrinks every gap between consecutive occupied ranks to at most
+/// [`GraphLabel::max_edge_length`], shifting later ranks down and keeping their relative order.
+///
+/// Returns whether any gap was compressed (also recorded in
+/// [`GraphLabel::edge_length_clamped`]); a no-op when `max_edge_length` is `None`.
+pub fn clamp_rank_gaps(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) -> bool {
+    let Some(max_len) = g.graph().max_edge_length else {
+        return false;
+    };
+    let max_gap = i32::try_from(max_len.max(2)).unwrap_or(i32::MAX);
+
+    let mut ranks: Vec<i32> = Vec::new();
+    g.for_each_node(|_id, n| {
+        if let Some(rank) = n.rank {
+            ranks.push(rank);
+        }
+    });
+    ranks.sort_unstable();
+    ranks.dedup();
+    if ranks.windows(2).all(|w| w[1] - w[0] <= max_gap) {
+        return false;
+    }
+
+    let mut new_ranks: Vec<i32> = Vec::with_capacity(ranks.len());
+    for (i, &rank) in ranks.iter().enumerate() {
+        let new_rank = match i.checked_sub(1) {
+            Some(prev) => new_ranks[prev] + (rank - ranks[prev]).min(max_gap),
+            None => rank,
+        };
+        new_ranks.push(new_rank);
+    }
+    g.for_each_node_mut(|_id, n| {
+        if let Some(rank) = n.rank
+            && let Ok(ix) = ranks.binary_search(&rank)
+        {
+            n.rank = Some(new_ranks[ix]);
+        }
+    });
+    g.graph_mut().edge_length_clamped = true;
+    true
+}
+
+/// Drops empty ranks that are not multiples of `node_rank_factor` (the nesting graph's border
+/// ranks). Ranks are measured from the current minimum, so this also works before
+/// [`normalize_ranks`]; it is a no-op without a positive `node_rank_factor`.
 pub fn remove_empty_ranks(g: &mut Graph<NodeLabel, EdgeLabel, GraphLabel>) {
     let Some(factor) = g.graph().node_rank_factor.filter(|&f| f > 0) else {
         return;
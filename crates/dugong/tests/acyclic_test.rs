@@ -73,6 +73,7 @@ fn acyclic_run_breaks_cycles_in_the_input_graph() {
         g.set_path(&["a", "b", "c", "d", "a"]);
         acyclic::run(&mut g);
         assert_eq!(alg::find_cycles(&g), Vec::<Vec<String>>::new());
+        assert_eq!(g.validate(), Ok(()));
     }
 }
 
@@ -164,6 +165,48 @@ fn acyclic_undo_can_restore_previously_reversed_edges() {
     }
 }
 
+#[test]
+fn acyclic_undo_restores_parallel_edges_with_their_names() {
+    for acyclicer in ["greedy", "dfs", "unknown-should-still-work"] {
+        let mut g = new_graph(acyclicer);
+        let parallel = [
+            ("a", "b", Some("x"), 1),
+            ("a", "b", Some("y"), 2),
+            ("a", "b", None, 3),
+            ("b", "a", Some("x"), 4),
+            ("b", "a", None, 5),
+            ("b", "c", None, 6),
+            ("c", "a", Some("back"), 7),
+        ];
+        for (v, w, name, minlen) in parallel {
+            g.set_edge_named(
+                v,
+                w,
+                name,
+                Some(EdgeLabel {
+                    minlen,
+                    weight: 1.0,
+                    ..Default::default()
+                }),
+            );
+        }
+        let original = strip_edges(&g);
+
+        acyclic::run(&mut g);
+        assert!(alg::find_cycles(&g).is_empty(), "{acyclicer}");
+        assert_eq!(g.edge_count(), parallel.len(), "{acyclicer}");
+
+        acyclic::undo(&mut g);
+        assert_eq!(strip_edges(&g), original, "{acyclicer}");
+        for (v, w, name, minlen) in parallel {
+            let label = g.edge(v, w, name).unwrap();
+            assert_eq!(label.minlen, minlen, "{acyclicer}: {v}->{w} {name:?}");
+            assert!(!label.reversed);
+            assert_eq!(label.forward_name, None);
+        }
+    }
+}
+
 #[test]
 fn acyclic_greedy_prefers_to_break_cycles_at_low_weight_edges() {
     let mut g = new_graph("greedy");
@@ -152,3 +152,43 @@ fn greedy_fas_works_for_multigraphs() {
         ]
     );
 }
+
+fn assert_breaks_all_cycles(mut g: Graph<(), i64, ()>, fas: Vec<dugong::graphlib::EdgeKey>) {
+    for e in &fas {
+        assert!(g.remove_edge_key(e), "fas edge {e:?} not in graph");
+    }
+    assert_eq!(alg::find_cycles(&g), Vec::<Vec<String>>::new());
+}
+
+#[test]
+fn greedy_fas_breaks_cycles_with_zero_weight_edges() {
+    let mut g: Graph<(), i64, ()> = Graph::new(GraphOptions::default());
+    g.set_edge_with_label("a", "b", 0);
+    g.set_edge_with_label("b", "c", 0);
+    g.set_edge_with_label("c", "a", 0);
+    g.set_edge_with_label("c", "d", 0);
+    g.set_edge_with_label("d", "e", 1);
+    g.set_edge_with_label("e", "d", 0);
+
+    let fas = greedy_fas::greedy_fas_with_weight(&g, |w: &i64| *w);
+    assert!(!fas.is_empty());
+    assert_breaks_all_cycles(g, fas);
+}
+
+#[test]
+fn greedy_fas_breaks_cycles_with_negative_weights() {
+    let mut g: Graph<(), i64, ()> = Graph::new(GraphOptions {
+        multigraph: true,
+        ..Default::default()
+    });
+    g.set_edge_named("a", "b", Some("x"), Some(-3));
+    g.set_edge_named("a", "b", Some("y"), Some(3));
+    g.set_edge_with_label("b", "c", -100);
+    g.set_edge_with_label("c", "a", 7);
+    g.set_edge_with_label("c", "d", -1);
+    g.set_edge_with_label("d", "b", 2);
+
+    let fas = greedy_fas::greedy_fas_with_weight(&g, |w: &i64| *w);
+    assert!(!fas.is_empty());
+    assert_breaks_all_cycles(g, fas);
+}
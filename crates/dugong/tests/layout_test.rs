@@ -225,6 +225,64 @@ fn layout_dagreish_can_layout_a_long_edge_with_a_label() {
     assert!(edge.y.unwrap() < g.node("b").unwrap().y.unwrap());
 }
 
+#[cfg(feature = "dagreish")]
+#[test]
+fn layout_dagreish_clamps_a_huge_minlen_edge_to_max_edge_length() {
+    let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph(GraphLabel {
+        max_edge_length: Some(4),
+        ..Default::default()
+    });
+    g.set_default_edge_label(EdgeLabel::default);
+
+    for id in ["a", "b", "c"] {
+        g.set_node(
+            id,
+            NodeLabel {
+                width: 50.0,
+                height: 50.0,
+                ..Default::default()
+            },
+        );
+    }
+    g.set_edge_with_label(
+        "a",
+        "b",
+        EdgeLabel {
+            width: 40.0,
+            height: 20.0,
+            minlen: 1_000_000,
+            labelpos: LabelPos::C,
+            ..Default::default()
+        },
+    );
+    g.set_edge("b", "c");
+
+    layout_dagreish(&mut g);
+
+    assert!(g.graph().edge_length_clamped);
+    assert_eq!(g.node_count(), 3);
+    let (a, b, c) = (
+        g.node("a").unwrap(),
+        g.node("b").unwrap(),
+        g.node("c").unwrap(),
+    );
+    assert!(a.y.unwrap() < b.y.unwrap() && b.y.unwrap() < c.y.unwrap());
+    // Four half-ranks (the label rank in the middle) instead of two million.
+    let ab_gap = b.y.unwrap() - a.y.unwrap();
+    assert!(ab_gap <= 50.0 + 4.0 * 25.0 + 20.0, "{ab_gap}");
+
+    let ab = g.edge("a", "b", None).unwrap();
+    assert!(ab.points.len() <= 5, "{:?}", ab.points);
+    assert!(ab.y.unwrap() > a.y.unwrap() && ab.y.unwrap() < b.y.unwrap());
+    let bc = g.edge("b", "c", None).unwrap();
+    assert!(!bc.points.is_empty());
+}
+
 #[cfg(feature = "dagreish")]
 #[test]
 fn layout_dagreish_can_layout_a_short_cycle() {
@@ -282,6 +340,83 @@ fn layout_dagreish_can_layout_a_short_cycle() {
     assert!(ba.points[0].y > ba.points[1].y);
 }
 
+#[cfg(feature = "dagreish")]
+#[test]
+fn layout_dagreish_keeps_edge_extras_on_long_and_reversed_edges() {
+    let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph(GraphLabel::default());
+    g.set_default_edge_label(EdgeLabel::default);
+
+    for id in ["a", "b", "c"] {
+        g.set_node(
+            id,
+            NodeLabel {
+                width: 50.0,
+                height: 50.0,
+                ..Default::default()
+            },
+        );
+    }
+
+    let flowchart_edge = |id: &str, label: &str| {
+        let mut edge = EdgeLabel {
+            width: 40.0,
+            height: 20.0,
+            minlen: 2,
+            ..Default::default()
+        };
+        edge.extras
+            .insert("id".to_string(), serde_json::Value::from(id));
+        edge.extras
+            .insert("label".to_string(), serde_json::Value::from(label));
+        edge.extras
+            .insert("labelType".to_string(), serde_json::Value::from("markdown"));
+        edge
+    };
+    g.set_edge_named(
+        "a",
+        "b",
+        Some("L_a_b_0"),
+        Some(flowchart_edge("L_a_b_0", "forward")),
+    );
+    g.set_edge_named(
+        "b",
+        "c",
+        Some("L_b_c_0"),
+        Some(flowchart_edge("L_b_c_0", "next")),
+    );
+    // Closes a cycle, so acyclic reverses one of the three edges before normalize splits them.
+    g.set_edge_named(
+        "c",
+        "a",
+        Some("L_c_a_0"),
+        Some(flowchart_edge("L_c_a_0", "back")),
+    );
+
+    layout_dagreish(&mut g);
+
+    assert_eq!(g.edge_count(), 3);
+    for (v, w, id, label) in [
+        ("a", "b", "L_a_b_0", "forward"),
+        ("b", "c", "L_b_c_0", "next"),
+        ("c", "a", "L_c_a_0", "back"),
+    ] {
+        let edge = g.edge(v, w, Some(id)).unwrap();
+        assert_eq!(edge.extras["id"], serde_json::Value::from(id));
+        assert_eq!(edge.extras["label"], serde_json::Value::from(label));
+        assert_eq!(
+            edge.extras["labelType"],
+            serde_json::Value::from("markdown")
+        );
+        assert!(edge.x.is_some());
+        assert!(!edge.points.is_empty());
+    }
+}
+
 #[test]
 fn layout_adds_rectangle_intersects_for_edges() {
     let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
@@ -861,3 +996,48 @@ fn layout_dagreish_keeps_left_edge_label_coordinates_in_graph_bounding_box_for_r
         assert!(edge.y.unwrap() + edge.height / 2.0 <= g.graph().height);
     }
 }
+
+#[cfg(feature = "dagreish")]
+#[test]
+fn layout_dagreish_spacing_scales_with_nodesep_and_ranksep() {
+    let run = |nodesep: f64, ranksep: f64| {
+        let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
+            multigraph: true,
+            compound: true,
+            ..Default::default()
+        });
+        g.set_graph(GraphLabel {
+            nodesep,
+            ranksep,
+            ..Default::default()
+        });
+        g.set_default_edge_label(EdgeLabel::default);
+        for v in ["r", "a", "b"] {
+            g.set_node(
+                v,
+                NodeLabel {
+                    width: 40.0,
+                    height: 20.0,
+                    ..Default::default()
+                },
+            );
+        }
+        g.set_edge("r", "a");
+        g.set_edge("r", "b");
+
+        layout_dagreish(&mut g);
+        let (r, a, b) = (
+            g.node("r").unwrap(),
+            g.node("a").unwrap(),
+            g.node("b").unwrap(),
+        );
+        let x_gap = (b.x.unwrap() - a.x.unwrap()).abs() - 40.0;
+        let y_gap = a.y.unwrap() - r.y.unwrap() - 20.0;
+        assert_eq!(a.y, b.y);
+        (x_gap, y_gap)
+    };
+
+    assert_eq!(run(50.0, 50.0), (50.0, 50.0));
+    assert_eq!(run(100.0, 50.0), (100.0, 50.0));
+    assert_eq!(run(50.0, 120.0), (50.0, 120.0));
+}
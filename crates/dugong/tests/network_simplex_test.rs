@@ -792,3 +792,38 @@ fn init_cut_values_works_for_updated_gansner_graph() {
     assert_eq!(t.edge("e", "g", None).unwrap().cutvalue, 1.0);
     assert_eq!(t.edge("f", "g", None).unwrap().cutvalue, 0.0);
 }
+
+#[test]
+fn network_simplex_with_max_pivots_stops_at_the_cap_with_a_feasible_ranking() {
+    let assert_feasible = |g: &Graph<NodeLabel, EdgeLabel, GraphLabel>| {
+        for e in g.edges() {
+            let v = g.node(&e.v).unwrap().rank.unwrap();
+            let w = g.node(&e.w).unwrap().rank.unwrap();
+            let minlen = g.edge_by_key(e).unwrap().minlen as i32;
+            assert!(w - v >= minlen, "edge {e:?} spans {}", w - v);
+        }
+    };
+
+    // The Gansner graph's initial feasible tree needs an exchange before it is optimal.
+    let mut capped = gansner_graph();
+    assert!(!rank::network_simplex::network_simplex_with_max_pivots(
+        &mut capped,
+        0
+    ));
+    assert_feasible(&capped);
+    assert!(capped.graph().rank_pivots_capped);
+
+    let mut full = gansner_graph();
+    assert!(rank::network_simplex::network_simplex_with_max_pivots(
+        &mut full,
+        usize::MAX
+    ));
+    assert_feasible(&full);
+    assert!(!full.graph().rank_pivots_capped);
+
+    // The default cap is generous enough for ordinary graphs to converge.
+    let mut g = gansner_graph();
+    ns(&mut g);
+    util::normalize_ranks(&mut full);
+    assert_eq!(rank_by_ix(&g), rank_by_ix(&full));
+}
@@ -78,6 +78,7 @@ fn normalize_run_splits_a_two_layer_edge_into_two_segments() {
     assert_eq!(g.node(&successor).unwrap().rank, Some(1));
     assert_eq!(g.successors(&successor), vec!["b"]);
     assert_eq!(g.node("a").unwrap().rank, Some(0));
+    assert_eq!(g.validate(), Ok(()));
     assert_eq!(g.node("b").unwrap().rank, Some(2));
 
     assert_eq!(g.graph().dummy_chains.len(), 1);
@@ -291,6 +292,49 @@ fn normalize_undo_restores_previous_edge_labels() {
     );
 }
 
+#[test]
+fn normalize_undo_restores_extras_on_a_long_labeled_edge() {
+    let mut g = new_graph();
+    g.set_node(
+        "a",
+        NodeLabel {
+            rank: Some(0),
+            ..Default::default()
+        },
+    );
+    g.set_node(
+        "b",
+        NodeLabel {
+            rank: Some(4),
+            ..Default::default()
+        },
+    );
+    let mut label = EdgeLabel {
+        width: 10.0,
+        height: 5.0,
+        label_rank: Some(2),
+        ..Default::default()
+    };
+    label
+        .extras
+        .insert("foo".to_string(), Value::String("bar".to_string()));
+    label.extras.insert("count".to_string(), Value::from(3));
+    let expected = label.extras.clone();
+    g.set_edge_named("a", "b", Some("multi"), Some(label));
+
+    normalize::run(&mut g);
+    // Segment edges only carry the weight, like dagre's normalize.
+    assert!(
+        g.edges()
+            .all(|e| g.edge_by_key(e).unwrap().extras.is_empty())
+    );
+
+    normalize::undo(&mut g);
+
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.edge("a", "b", Some("multi")).unwrap().extras, expected);
+}
+
 #[test]
 fn normalize_undo_collects_assigned_coordinates_into_points() {
     let mut g = new_graph();
@@ -465,6 +509,71 @@ fn normalize_undo_sets_coords_and_dims_for_the_label_if_the_long_edge_has_one()
     assert_eq!(e.height, 10.0);
 }
 
+#[test]
+fn normalize_undo_collects_points_in_rank_order_around_a_mid_chain_label() {
+    let mut g = new_graph();
+    g.set_node(
+        "a",
+        NodeLabel {
+            rank: Some(0),
+            ..Default::default()
+        },
+    );
+    g.set_node(
+        "b",
+        NodeLabel {
+            rank: Some(4),
+            ..Default::default()
+        },
+    );
+    g.set_edge_with_label(
+        "a",
+        "b",
+        EdgeLabel {
+            width: 10.0,
+            height: 20.0,
+            label_rank: Some(2),
+            ..Default::default()
+        },
+    );
+
+    normalize::run(&mut g);
+
+    // Walk the dummy chain and assign coordinates that encode the rank, including a label dummy
+    // with distinct dimensions in the middle of the chain.
+    let mut v = g.successors("a")[0].to_string();
+    while v != "b" {
+        let next = g.successors(&v)[0].to_string();
+        let node = g.node_mut(&v).unwrap();
+        let rank = node.rank.unwrap() as f64;
+        node.x = Some(rank * 5.0);
+        node.y = Some(rank * 100.0);
+        if node.dummy.as_deref() == Some("edge-label") {
+            node.width = 30.0;
+            node.height = 15.0;
+        }
+        v = next;
+    }
+
+    normalize::undo(&mut g);
+
+    let e = g.edge("a", "b", None).unwrap();
+    assert_eq!(
+        e.points,
+        vec![
+            Point { x: 5.0, y: 100.0 },
+            Point { x: 10.0, y: 200.0 },
+            Point { x: 15.0, y: 300.0 },
+        ]
+    );
+    assert!(e.points.windows(2).all(|w| w[0].y < w[1].y));
+    assert_eq!(e.x, Some(10.0));
+    assert_eq!(e.y, Some(200.0));
+    assert_eq!(e.width, 30.0);
+    assert_eq!(e.height, 15.0);
+    assert_eq!(g.node_count(), 2);
+}
+
 #[test]
 fn normalize_undo_restores_multi_edges() {
     let mut g = new_graph();
@@ -513,3 +622,27 @@ fn normalize_undo_restores_multi_edges() {
         vec![Point { x: 15.0, y: 20.0 }]
     );
 }
+
+#[test]
+fn normalize_run_without_max_edge_length_keeps_one_dummy_per_rank() {
+    let mut g = new_graph();
+    g.set_node(
+        "a",
+        NodeLabel {
+            rank: Some(0),
+            ..Default::default()
+        },
+    );
+    g.set_node(
+        "b",
+        NodeLabel {
+            rank: Some(10),
+            ..Default::default()
+        },
+    );
+    g.set_edge_with_label("a", "b", EdgeLabel::default());
+
+    normalize::run(&mut g);
+
+    assert_eq!(g.node_count(), 11);
+}
@@ -1,6 +1,6 @@
 use dugong::NodeLabel;
 use dugong::graphlib::{Graph, GraphOptions};
-use dugong::order::{OrderOptions, WeightLabel, cross_count, order};
+use dugong::order::{Heuristic, OrderOptions, WeightLabel, cross_count, order, order_with_report};
 use dugong::util;
 
 fn new_graph() -> Graph<NodeLabel, WeightLabel, ()> {
@@ -166,8 +166,105 @@ fn order_can_skip_the_optimal_ordering() {
         &mut g,
         OrderOptions {
             disable_optimal_order_heuristic: true,
+            ..Default::default()
+        },
+    );
+    let layering = util::build_layer_matrix(&g);
+    assert_eq!(cross_count(&g, &layering), 1.0);
+}
+
+#[test]
+fn order_with_report_returns_crossings_of_the_assigned_layering() {
+    let build = || {
+        let mut g = new_graph();
+        for (v, rank) in [("a", 1), ("b", 2), ("d", 2), ("c", 3), ("e", 3)] {
+            g.set_node(
+                v,
+                NodeLabel {
+                    rank: Some(rank),
+                    ..Default::default()
+                },
+            );
+        }
+        set_path(&mut g, &["a", "b", "c"]);
+        g.set_edge("a", "d");
+        g.set_edge("b", "e");
+        g.set_edge("d", "c");
+        g
+    };
+
+    let mut plain = build();
+    order(&mut plain, OrderOptions::default());
+
+    let mut g = build();
+    let report = order_with_report(&mut g, OrderOptions::default());
+    let layering = util::build_layer_matrix(&g);
+    assert_eq!(report.crossings, cross_count(&g, &layering));
+    assert_eq!(report.crossings, 0.0);
+    assert!(report.iterations >= 4);
+    assert_eq!(layering, util::build_layer_matrix(&plain));
+
+    let mut g = build();
+    let report = order_with_report(
+        &mut g,
+        OrderOptions {
+            disable_optimal_order_heuristic: true,
+            ..Default::default()
         },
     );
+    assert_eq!(report.crossings, 1.0);
+    assert_eq!(report.iterations, 0);
+
+    let mut empty = new_graph();
+    assert_eq!(
+        order_with_report(&mut empty, OrderOptions::default()).crossings,
+        0.0
+    );
+}
+
+#[test]
+fn order_median_heuristic_can_beat_barycenter() {
+    let build = || {
+        let mut g = new_graph();
+        for v in ["a", "b", "c"] {
+            g.set_node(
+                v,
+                NodeLabel {
+                    rank: Some(0),
+                    ..Default::default()
+                },
+            );
+        }
+        for v in ["w", "x", "y", "z"] {
+            g.set_node(
+                v,
+                NodeLabel {
+                    rank: Some(1),
+                    ..Default::default()
+                },
+            );
+        }
+        g.set_edge("a", "w");
+        g.set_edge("c", "w");
+        g.set_edge("c", "x");
+        g.set_edge("b", "z");
+        g.set_edge("c", "z");
+        g
+    };
+
+    let mut g = build();
+    order(&mut g, OrderOptions::default());
     let layering = util::build_layer_matrix(&g);
     assert_eq!(cross_count(&g, &layering), 1.0);
+
+    let mut g = build();
+    order(
+        &mut g,
+        OrderOptions {
+            heuristic: Heuristic::Median,
+            ..Default::default()
+        },
+    );
+    let layering = util::build_layer_matrix(&g);
+    assert_eq!(cross_count(&g, &layering), 0.0);
 }
@@ -231,6 +231,7 @@ fn parent_dummy_chains_handles_nested_subgraphs() {
     assert_eq!(g.parent("d3"), None);
     assert_eq!(g.parent("d4"), Some("sg3"));
     assert_eq!(g.parent("d5"), Some("sg4"));
+    assert_eq!(g.validate(), Ok(()));
 }
 
 #[test]
@@ -1,6 +1,6 @@
 use dugong::graphlib::{Graph, GraphOptions};
-use dugong::rank;
 use dugong::{EdgeLabel, GraphLabel, NodeLabel};
+use dugong::{normalize, order, rank};
 
 fn gansner_graph() -> Graph<NodeLabel, EdgeLabel, GraphLabel> {
     let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions::default());
@@ -85,3 +85,55 @@ fn rank_can_rank_a_single_node_graph_for_each_ranker() {
         assert_eq!(g.node("a").unwrap().rank, Some(0));
     }
 }
+
+#[test]
+fn rank_none_preserves_pre_assigned_ranks_for_normalize_and_order() {
+    let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph(GraphLabel {
+        ranker: Some("none".to_string()),
+        ..Default::default()
+    });
+    for (v, r) in [("a", 0), ("b", 3), ("c", 1), ("d", 3)] {
+        g.set_node(
+            v,
+            NodeLabel {
+                rank: Some(r),
+                ..Default::default()
+            },
+        );
+    }
+    g.set_edge("a", "b");
+    g.set_edge("a", "c");
+    g.set_edge("c", "d");
+
+    rank::rank(&mut g);
+    for (v, r) in [("a", 0), ("b", 3), ("c", 1), ("d", 3)] {
+        assert_eq!(g.node(v).unwrap().rank, Some(r), "rank of {v}");
+    }
+
+    // Long edges spanning the custom ranks get one dummy node per intermediate rank.
+    normalize::run(&mut g);
+    assert_eq!(g.graph().dummy_chains.len(), 2);
+    assert_eq!(g.node_count(), 4 + 2 + 1);
+    for e in g.edges() {
+        let v_rank = g.node(&e.v).unwrap().rank.unwrap();
+        let w_rank = g.node(&e.w).unwrap().rank.unwrap();
+        assert_eq!(w_rank - v_rank, 1, "edge {} -> {}", e.v, e.w);
+    }
+
+    order::order(&mut g, order::OrderOptions::default());
+    for r in 0..=3 {
+        let mut orders: Vec<usize> = g
+            .nodes()
+            .filter_map(|v| g.node(v))
+            .filter(|n| n.rank == Some(r))
+            .map(|n| n.order.unwrap())
+            .collect();
+        orders.sort_unstable();
+        assert_eq!(orders, (0..orders.len()).collect::<Vec<_>>(), "rank {r}");
+    }
+}
@@ -48,6 +48,33 @@ fn longest_path_can_assign_ranks_to_connected_nodes() {
     assert_eq!(g.node("b").unwrap().rank, Some(1));
 }
 
+#[test]
+fn longest_path_terminates_on_a_two_cycle_without_sources() {
+    let mut g = new_graph();
+    g.set_edge("a", "b");
+    g.set_edge("b", "a");
+
+    rank::util::longest_path(&mut g);
+    util::normalize_ranks(&mut g);
+
+    // The back edge into the DFS path (`b -> a`) is ignored.
+    assert_eq!(g.node("a").unwrap().rank, Some(0));
+    assert_eq!(g.node("b").unwrap().rank, Some(1));
+}
+
+#[test]
+fn longest_path_terminates_on_a_cycle_reachable_from_a_source() {
+    let mut g = new_graph();
+    g.set_path(&["s", "a", "b", "a"]);
+
+    rank::util::longest_path(&mut g);
+    util::normalize_ranks(&mut g);
+
+    assert_eq!(g.node("s").unwrap().rank, Some(0));
+    assert_eq!(g.node("a").unwrap().rank, Some(1));
+    assert_eq!(g.node("b").unwrap().rank, Some(2));
+}
+
 #[test]
 fn longest_path_can_assign_ranks_for_a_diamond() {
     let mut g = new_graph();
@@ -263,6 +263,48 @@ fn util_predecessor_weights_maps_a_node_to_its_predecessors_with_associated_weig
     );
 }
 
+#[test]
+fn util_total_successor_and_predecessor_weight_sum_incident_edge_weights() {
+    let mut g: Graph<NodeLabel, EdgeLabel, serde_json::Value> = Graph::new(GraphOptions {
+        multigraph: true,
+        compound: false,
+        ..Default::default()
+    });
+    for (v, w, name, weight) in [
+        ("a", "b", None, 2.0),
+        ("b", "c", None, 1.0),
+        ("b", "c", Some("multi"), 2.0),
+        ("b", "d", Some("multi"), 1.0),
+    ] {
+        g.set_edge_named(
+            v,
+            w,
+            name,
+            Some(EdgeLabel {
+                weight,
+                ..Default::default()
+            }),
+        );
+    }
+
+    let weight = |label: &EdgeLabel| label.weight;
+    assert_eq!(util::total_successor_weight(&g, "a", weight), 2.0);
+    assert_eq!(util::total_successor_weight(&g, "b", weight), 4.0);
+    assert_eq!(util::total_successor_weight(&g, "c", weight), 0.0);
+    assert_eq!(util::total_predecessor_weight(&g, "b", weight), 2.0);
+    assert_eq!(util::total_predecessor_weight(&g, "c", weight), 3.0);
+    assert_eq!(util::total_predecessor_weight(&g, "a", weight), 0.0);
+    assert_eq!(util::total_successor_weight(&g, "missing", weight), 0.0);
+
+    let nested = util::successor_weights(&g);
+    for v in g.node_ids() {
+        assert_eq!(
+            util::total_successor_weight(&g, &v, weight),
+            nested[&v].values().sum::<f64>()
+        );
+    }
+}
+
 fn expect_intersects(rect: util::Rect, point: Point) {
     let cross = util::intersect_rect(rect, point);
     if cross.x != point.x {
@@ -459,6 +501,36 @@ fn util_normalize_ranks_works_for_negative_ranks() {
     assert_eq!(g.node("b").unwrap().rank, Some(1));
 }
 
+#[test]
+fn util_clamp_rank_gaps_compresses_gaps_longer_than_max_edge_length() {
+    let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions::default());
+    g.set_graph(GraphLabel {
+        max_edge_length: Some(4),
+        ..Default::default()
+    });
+    for (id, rank) in [("a", 0), ("b", 3), ("c", 1_000_000), ("d", 1_000_002)] {
+        g.set_node(
+            id,
+            NodeLabel {
+                rank: Some(rank),
+                ..Default::default()
+            },
+        );
+    }
+
+    assert!(util::clamp_rank_gaps(&mut g));
+    assert!(g.graph().edge_length_clamped);
+    let ranks: Vec<_> = ["a", "b", "c", "d"]
+        .iter()
+        .map(|id| g.node(id).unwrap().rank)
+        .collect();
+    assert_eq!(ranks, vec![Some(0), Some(3), Some(7), Some(9)]);
+
+    // Already within the cap: untouched.
+    assert!(!util::clamp_rank_gaps(&mut g));
+    assert_eq!(g.node("d").unwrap().rank, Some(9));
+}
+
 #[test]
 fn util_normalize_ranks_does_not_assign_a_rank_to_subgraphs() {
     let mut g: Graph<NodeLabel, EdgeLabel, serde_json::Value> = Graph::new(GraphOptions {
@@ -481,6 +553,83 @@ fn util_normalize_ranks_does_not_assign_a_rank_to_subgraphs() {
     assert_eq!(g.node("a").unwrap().rank, Some(0));
 }
 
+fn negative_rank_graph() -> Graph<NodeLabel, EdgeLabel, GraphLabel> {
+    let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
+        multigraph: false,
+        compound: true,
+        ..Default::default()
+    });
+    g.set_graph(GraphLabel {
+        node_rank_factor: Some(2),
+        ..Default::default()
+    });
+    for (id, rank) in [("a", -8), ("b", -7), ("c", -3), ("d", -2)] {
+        g.set_node(
+            id,
+            NodeLabel {
+                rank: Some(rank),
+                ..Default::default()
+            },
+        );
+    }
+    g.set_node("sg", NodeLabel::default());
+    g.set_parent("a", "sg");
+    g
+}
+
+fn ranks(g: &Graph<NodeLabel, EdgeLabel, GraphLabel>) -> Vec<(&str, Option<i32>)> {
+    ["a", "b", "c", "d", "sg"]
+        .into_iter()
+        .map(|id| (id, g.node(id).unwrap().rank))
+        .collect()
+}
+
+#[test]
+fn util_normalize_ranks_then_remove_empty_ranks_handles_negative_minimum_rank() {
+    let mut g = negative_rank_graph();
+
+    util::normalize_ranks(&mut g);
+    assert_eq!(
+        ranks(&g),
+        vec![
+            ("a", Some(0)),
+            ("b", Some(1)),
+            ("c", Some(5)),
+            ("d", Some(6)),
+            ("sg", None)
+        ]
+    );
+
+    // Empty rank 3 is not a multiple of the factor and gets dropped; empty rank 2 and 4 are
+    // border ranks and stay.
+    util::remove_empty_ranks(&mut g);
+    assert_eq!(
+        ranks(&g),
+        vec![
+            ("a", Some(0)),
+            ("b", Some(1)),
+            ("c", Some(4)),
+            ("d", Some(5)),
+            ("sg", None)
+        ]
+    );
+}
+
+#[test]
+fn util_remove_empty_ranks_before_normalize_ranks_matches_normalized_order() {
+    // The dagreish pipeline removes empty ranks before normalizing; starting from negative ranks
+    // must give the same result as the opposite order.
+    let mut g = negative_rank_graph();
+
+    util::remove_empty_ranks(&mut g);
+    util::normalize_ranks(&mut g);
+
+    let mut expected = negative_rank_graph();
+    util::normalize_ranks(&mut expected);
+    util::remove_empty_ranks(&mut expected);
+    assert_eq!(ranks(&g), ranks(&expected));
+}
+
 #[test]
 fn util_remove_empty_ranks_removes_border_ranks_without_any_nodes() {
     let mut g: Graph<NodeLabel, EdgeLabel, GraphLabel> = Graph::new(GraphOptions {
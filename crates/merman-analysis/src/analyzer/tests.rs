@@ -205,11 +205,13 @@ fn rich_facts_mode_reports_editor_facts_preprocess_failure() {
                 config: MermaidConfig::default(),
                 effective_config: MermaidConfig::default(),
                 title: None,
+                warnings: Vec::new(),
             },
             model: json!({
                 "type": "flowchart-v2",
                 "nodes": []
             }),
+            spans: None,
         }),
         Vec::new(),
         super::AnalysisMode::RichFacts,
@@ -278,6 +280,7 @@ fn malformed_flowchart_parsed_diagram() -> ParsedDiagram {
             config: MermaidConfig::default(),
             effective_config: MermaidConfig::default(),
             title: None,
+            warnings: Vec::new(),
         },
         model: json!({
             "type": "flowchart-v2",
@@ -285,6 +288,7 @@ fn malformed_flowchart_parsed_diagram() -> ParsedDiagram {
                 { "id": 1 }
             ]
         }),
+        spans: None,
     }
 }
 
@@ -98,6 +98,13 @@ pub(crate) fn core_error_diagnostic(
             diagram_type: None,
             parse_location: None,
         },
+        CoreError::InputTooLarge { size, limit } => CoreErrorDiagnostic {
+            diagnostic: Some(crate::source_limits::source_limit_diagnostic_for_len(
+                size, limit,
+            )),
+            diagram_type: None,
+            parse_location: None,
+        },
     }
 }
 
@@ -8,6 +8,7 @@ use crate::{
 use merman_core::{
     BLOCK_WIDTH_WARNING_RULE_ID, DiagramWarningFact, FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID,
     FLOWCHART_UNKNOWN_STYLE_TARGET_WARNING_RULE_ID, GIT_GRAPH_DUPLICATE_COMMIT_WARNING_RULE_ID,
+    SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -37,6 +38,7 @@ pub const FLOWCHART_EXPLICIT_DIRECTION_RULE_ID: &str =
 pub const FLOWCHART_UNKNOWN_STYLE_TARGET_RULE_ID: &str =
     "merman.semantic.flowchart.unknown_style_target";
 pub const GIT_GRAPH_DUPLICATE_COMMIT_RULE_ID: &str = "merman.git_graph.duplicate_commit_id";
+pub const SEQUENCE_INVALID_ACTOR_JSON_RULE_ID: &str = "merman.semantic.sequence.invalid_actor_json";
 pub const RULE_CATALOG_RESPONSE_VERSION: u32 = 1;
 
 const DEPRECATED_FLOWCHART_HTML_LABELS_INIT_CONFIG_PATHS: [&[&str]; 1] =
@@ -439,6 +441,19 @@ const GIT_GRAPH_DUPLICATE_COMMIT_RULE: RuleDescriptor = RuleDescriptor {
     origin: RuleOrigin::MermaidCompatibility,
     fixable: false,
 };
+const SEQUENCE_INVALID_ACTOR_JSON_RULE: RuleDescriptor = RuleDescriptor {
+    id: SEQUENCE_INVALID_ACTOR_JSON_RULE_ID,
+    description: "Report sequence `links` and `properties` payloads that are not valid JSON.",
+    evidence: &[
+        "https://github.com/mermaid-js/mermaid/blob/41646dfd43ac83f001b03c70605feb036afae46d/packages/mermaid/src/diagrams/sequence/sequenceDb.ts",
+    ],
+    default_severity: DiagnosticSeverity::Warning,
+    category: DiagnosticCategory::Semantic,
+    default_enabled: true,
+    default_profile: AnalysisRuleProfile::Core,
+    origin: RuleOrigin::MermaidCompatibility,
+    fixable: false,
+};
 const RULE_DESCRIPTORS: &[RuleDescriptor] = &[
     PREFER_INIT_DIRECTIVE_RULE,
     PREFER_FRONTMATTER_CONFIG_RULE,
@@ -459,6 +474,7 @@ const RULE_DESCRIPTORS: &[RuleDescriptor] = &[
     FLOWCHART_EXPLICIT_DIRECTION_RULE,
     FLOWCHART_UNKNOWN_STYLE_TARGET_RULE,
     GIT_GRAPH_DUPLICATE_COMMIT_RULE,
+    SEQUENCE_INVALID_ACTOR_JSON_RULE,
 ];
 
 pub fn rule_descriptors() -> &'static [RuleDescriptor] {
@@ -759,6 +775,7 @@ fn warning_fact_rule_descriptor(rule_id: &str) -> Option<RuleDescriptor> {
         FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID => Some(FLOWCHART_EXPLICIT_DIRECTION_RULE),
         FLOWCHART_UNKNOWN_STYLE_TARGET_WARNING_RULE_ID => Some(FLOWCHART_UNKNOWN_STYLE_TARGET_RULE),
         GIT_GRAPH_DUPLICATE_COMMIT_WARNING_RULE_ID => Some(GIT_GRAPH_DUPLICATE_COMMIT_RULE),
+        SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID => Some(SEQUENCE_INVALID_ACTOR_JSON_RULE),
         _ => None,
     }
 }
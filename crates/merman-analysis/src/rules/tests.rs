@@ -666,7 +666,7 @@ fn rule_config_can_override_block_warning_severity() {
 fn rule_descriptors_expose_stable_rule_metadata() {
     let descriptors = rule_descriptors();
 
-    assert_eq!(descriptors.len(), 19);
+    assert_eq!(descriptors.len(), 20);
     assert_eq!(descriptors[0].id, PREFER_INIT_DIRECTIVE_RULE_ID);
     assert!(descriptors[0].description.contains("canonical `init`"));
     assert_eq!(descriptors[0].default_severity, DiagnosticSeverity::Hint);
@@ -878,6 +878,11 @@ fn rule_descriptors_expose_stable_rule_metadata() {
             .iter()
             .any(|descriptor| descriptor.id == GIT_GRAPH_DUPLICATE_COMMIT_RULE_ID)
     );
+    assert!(
+        descriptors
+            .iter()
+            .any(|descriptor| descriptor.id == SEQUENCE_INVALID_ACTOR_JSON_RULE_ID)
+    );
 }
 
 #[test]
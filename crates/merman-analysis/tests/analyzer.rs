@@ -719,6 +719,24 @@ fn git_graph_duplicate_commit_id_is_warning() {
     assert!(diagnostic.message.contains("already exists"));
 }
 
+#[test]
+fn sequence_invalid_actor_json_is_warning() {
+    let payload =
+        analyze("sequenceDiagram\nparticipant a\nlinks a: { \"Repo\": \"https://repo\", }\n");
+
+    assert!(payload.valid);
+    assert_eq!(payload.summary.warnings, 1);
+    let diagnostic = &payload.diagnostics[0];
+    assert_eq!(diagnostic.id, "merman.semantic.sequence.invalid_actor_json");
+    assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+    assert_eq!(diagnostic.category, DiagnosticCategory::Semantic);
+    assert!(
+        diagnostic
+            .message
+            .contains("Invalid JSON in links for actor 'a'")
+    );
+}
+
 #[test]
 fn block_width_overflow_is_warning() {
     let payload = analyze("block-beta\n  columns 1\n  A:1\n  B:2\n  C:3\n");
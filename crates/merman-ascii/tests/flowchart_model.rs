@@ -169,9 +169,13 @@ fn single_node_flowchart_model(layout_shape: &str, label: &str) -> FlowchartV2Mo
             asset_height: None,
             classes: Vec::new(),
             styles: Vec::new(),
+            resolved_styles: Vec::new(),
             link: None,
             link_target: None,
+            link_target_effective: None,
             have_callback: false,
+            callback: None,
+            callback_effective: false,
         }],
         edges: Vec::new(),
         subgraphs: Vec::new(),
@@ -763,9 +767,13 @@ fn render_model_subgraph_direction_override_renders_local_left_right_layout_with
                 asset_height: None,
                 classes: Vec::new(),
                 styles: Vec::new(),
+                resolved_styles: Vec::new(),
                 link: None,
                 link_target: None,
+                link_target_effective: None,
                 have_callback: false,
+                callback: None,
+                callback_effective: false,
             },
             merman_core::diagrams::flowchart::FlowNode {
                 id: "B".to_string(),
@@ -781,9 +789,13 @@ fn render_model_subgraph_direction_override_renders_local_left_right_layout_with
                 asset_height: None,
                 classes: Vec::new(),
                 styles: Vec::new(),
+                resolved_styles: Vec::new(),
                 link: None,
                 link_target: None,
+                link_target_effective: None,
                 have_callback: false,
+                callback: None,
+                callback_effective: false,
             },
         ],
         edges: vec![merman_core::diagrams::flowchart::FlowEdge {
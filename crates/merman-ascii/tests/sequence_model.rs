@@ -424,6 +424,7 @@ fn basic_sequence_model() -> SequenceDiagramRenderModel {
             wrap: false,
             links: Default::default(),
             properties: Default::default(),
+            box_index: None,
         },
     );
 
@@ -438,6 +439,8 @@ fn basic_sequence_model() -> SequenceDiagramRenderModel {
         notes: Vec::new(),
         created_actors: Default::default(),
         destroyed_actors: Default::default(),
+        config: Default::default(),
+        warning_facts: Vec::new(),
     }
 }
 
@@ -452,6 +455,7 @@ fn add_sequence_participant(model: &mut SequenceDiagramRenderModel, id: &str) {
             wrap: false,
             links: Default::default(),
             properties: Default::default(),
+            box_index: None,
         },
     );
 }
@@ -478,6 +482,7 @@ fn message(from: Option<&str>, to: Option<&str>, message_type: i32) -> SequenceM
         activate: false,
         placement: None,
         central_connection: 0,
+        depth: None,
     }
 }
 
@@ -585,6 +590,7 @@ fn sequence_multiline_notes_render_from_typed_model() {
         activate: false,
         placement: Some(1),
         central_connection: 0,
+        depth: None,
     });
 
     let rendered = render_sequence_model(&model, &AsciiRenderOptions::ascii())
@@ -1802,6 +1808,7 @@ fn sequence_box_with_lifecycle_and_mirror_keeps_boundaries() {
         activate: false,
         placement: None,
         central_connection: 0,
+        depth: None,
     });
     model.messages.push(SequenceMessage {
         id: "m1".to_string(),
@@ -1813,6 +1820,7 @@ fn sequence_box_with_lifecycle_and_mirror_keeps_boundaries() {
         activate: false,
         placement: None,
         central_connection: 0,
+        depth: None,
     });
     model.messages.push(SequenceMessage {
         id: "m2".to_string(),
@@ -1824,6 +1832,7 @@ fn sequence_box_with_lifecycle_and_mirror_keeps_boundaries() {
         activate: false,
         placement: None,
         central_connection: 0,
+        depth: None,
     });
     model.messages.push(SequenceMessage {
         id: "m3".to_string(),
@@ -1835,6 +1844,7 @@ fn sequence_box_with_lifecycle_and_mirror_keeps_boundaries() {
         activate: false,
         placement: None,
         central_connection: 0,
+        depth: None,
     });
     model.messages.push(message(None, None, LINETYPE_LOOP_END));
     model.created_actors.insert("C".to_string(), 1);
@@ -229,6 +229,7 @@ fn lint_analyzer_options(
 ) -> Result<merman_analysis::AnalysisOptions, CliError> {
     let parse = merman::ParseOptions {
         suppress_errors: false,
+        ..Default::default()
     };
     let site_config = site_config_for(
         &crate::cli::ParseCliArgs {
@@ -53,6 +53,7 @@ pub(crate) fn site_config_for(
 pub(crate) fn parse_options(parse: &ParseCliArgs) -> ParseOptions {
     ParseOptions {
         suppress_errors: parse.suppress_errors,
+        ..Default::default()
     }
 }
 
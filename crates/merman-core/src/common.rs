@@ -80,6 +80,63 @@ pub fn parse_generic_types(input: &str) -> String {
     output.join("")
 }
 
+/// Wraps `text` to at most `width_chars` characters per line, following Mermaid's `wrapLabel`
+/// with character counts standing in for measured pixel widths.
+///
+/// Words are separated by ASCII spaces (runs collapse); a word longer than the width is
+/// hard-broken with a trailing `-` on every piece but the last. Text that already contains
+/// `<br>` breaks or `\n` escapes is split on those instead, as Mermaid skips wrapping then.
+pub fn wrap_text(text: &str, width_chars: usize) -> Vec<String> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    if width_chars == 0 {
+        return vec![text.to_string()];
+    }
+    let rows = crate::sanitize::split_mermaid_rows(text);
+    if rows.len() > 1 {
+        return rows;
+    }
+
+    let words: Vec<&str> = text.split(' ').filter(|w| !w.is_empty()).collect();
+    if words.is_empty() {
+        return vec![text.to_string()];
+    }
+
+    let mut completed: Vec<String> = Vec::new();
+    let mut next_line = String::new();
+    for word in words {
+        // Upstream measures `${word} ` (with the trailing space) against the limit.
+        let word_len = word.chars().count() + 1;
+        let next_len = next_line.chars().count();
+        if word_len > width_chars {
+            completed.push(std::mem::take(&mut next_line));
+            let chars: Vec<char> = word.chars().collect();
+            let mut current = String::new();
+            for (idx, ch) in chars.iter().enumerate() {
+                current.push(*ch);
+                if current.chars().count() >= width_chars {
+                    if idx + 1 < chars.len() {
+                        current.push('-');
+                    }
+                    completed.push(std::mem::take(&mut current));
+                }
+            }
+            next_line = current;
+        } else if next_len + word_len >= width_chars {
+            completed.push(std::mem::replace(&mut next_line, word.to_string()));
+        } else if next_line.is_empty() {
+            next_line = word.to_string();
+        } else {
+            next_line.push(' ');
+            next_line.push_str(word);
+        }
+    }
+    completed.push(next_line);
+
+    completed.into_iter().filter(|l| !l.is_empty()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +161,32 @@ mod tests {
             assert_eq!(parse_generic_types(input), expected);
         }
     }
+
+    #[test]
+    fn wrap_text_breaks_on_spaces_at_the_width_boundary() {
+        assert_eq!(wrap_text("", 10), Vec::<String>::new());
+        assert_eq!(wrap_text("short", 10), vec!["short"]);
+        // `word ` counts its trailing space, so a line is closed once it would reach the width.
+        assert_eq!(wrap_text("aaaa bbbb", 10), vec!["aaaa bbbb"]);
+        assert_eq!(wrap_text("aaaa bbbb", 9), vec!["aaaa", "bbbb"]);
+        assert_eq!(
+            wrap_text("the  quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(wrap_text("unchanged", 0), vec!["unchanged"]);
+    }
+
+    #[test]
+    fn wrap_text_hard_breaks_long_tokens_and_keeps_explicit_breaks() {
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd-", "efgh-", "ij"]);
+        assert_eq!(wrap_text("abcdefgh", 4), vec!["abcd-", "efgh"]);
+        assert_eq!(
+            wrap_text("hi abcdefgh yo", 4),
+            vec!["hi", "abcd-", "efgh", "yo"]
+        );
+        assert_eq!(
+            wrap_text("first line<br/>second line", 4),
+            vec!["first line", "second line"]
+        );
+    }
 }
@@ -52,6 +52,35 @@ pub(crate) fn sanitize_acc_descr(s: &str, config: &MermaidConfig) -> String {
     collapse_newline_whitespace(&sanitize_text(s, config))
 }
 
+/// Collects an `accDescr { ... }` block for line-based parsers. `rest` is the text after the
+/// `accDescr` keyword; text after `{` on that line counts, and the block ends at the first `}`
+/// (on the same line or a later one from `lines`). Returns `None` when `rest` does not open a
+/// block. Indentation and blank lines are left for [`sanitize_acc_descr`] to collapse.
+pub(crate) fn collect_acc_descr_block<'a>(
+    rest: &str,
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let rest = rest.trim_start().strip_prefix('{')?;
+
+    let mut buf = String::new();
+    if let Some(end) = rest.find('}') {
+        buf.push_str(&rest[..end]);
+        return Some(buf.trim().to_string());
+    }
+    buf.push_str(rest);
+    buf.push('\n');
+
+    for line in lines {
+        if let Some(end) = line.find('}') {
+            buf.push_str(&line[..end]);
+            break;
+        }
+        buf.push_str(line);
+        buf.push('\n');
+    }
+    Some(buf.trim().to_string())
+}
+
 pub(crate) fn sanitize_optional_title(value: &mut Option<String>, config: &MermaidConfig) {
     if let Some(s) = value.as_deref() {
         *value = Some(sanitize_text(s, config));
@@ -111,6 +111,32 @@ impl MermaidConfig {
         deep_merge_value(base, other);
     }
 
+    /// Merges the per-diagram config layers onto `base` in Mermaid's precedence order:
+    /// site config < front-matter `config:` < `%%{init}%%` directives.
+    ///
+    /// `base` plays the role of the site config, so its `secure` keys are stripped from both
+    /// layers before merging. Non-object layers are ignored. Theme palettes and diagram-type
+    /// defaults are applied later in the parse pipeline and are not included here.
+    pub fn merge_layers(base: &MermaidConfig, frontmatter: &Value, directive: &Value) -> Self {
+        let mut overrides = MermaidConfig::empty_object();
+        for layer in [frontmatter, directive] {
+            if layer.is_object() {
+                overrides.deep_merge(layer);
+            }
+        }
+        base.with_secure_overrides(&overrides)
+    }
+
+    pub(crate) fn with_secure_overrides(&self, overrides: &MermaidConfig) -> MermaidConfig {
+        let mut merged = self.clone();
+        if overrides.is_empty_object() {
+            return merged;
+        }
+        let filtered = merged.secure_filtered_overrides(overrides);
+        merged.deep_merge(filtered.as_value());
+        merged
+    }
+
     pub(crate) fn secure_filtered_overrides(&self, overrides: &MermaidConfig) -> MermaidConfig {
         let mut filtered = clone_value_nonrecursive(overrides.as_value());
         remove_secure_keys_recursive(self.as_value(), &mut filtered);
@@ -455,4 +481,43 @@ mod tests {
         assert_eq!(filtered.get_str("securityLevel"), Some("loose"));
         assert_eq!(filtered.get_str("theme"), Some("dark"));
     }
+
+    #[test]
+    fn merge_layers_applies_site_then_frontmatter_then_directive() {
+        let site_config = crate::generated::default_site_config();
+        let frontmatter = json!({
+            "theme": "forest",
+            "flowchart": { "htmlLabels": true }
+        });
+        let directive = json!({
+            "theme": "base",
+            "securityLevel": "loose"
+        });
+
+        let merged = MermaidConfig::merge_layers(&site_config, &frontmatter, &directive);
+
+        assert_eq!(merged.get_str("theme"), Some("base"));
+        assert_eq!(merged.get_bool("flowchart.htmlLabels"), Some(true));
+        // `securityLevel` is a default secure key, so the site value wins over the directive.
+        assert_eq!(
+            merged.get_str("securityLevel"),
+            site_config.get_str("securityLevel")
+        );
+        assert_eq!(
+            merged.as_value()["flowchart"]["curve"],
+            site_config.as_value()["flowchart"]["curve"]
+        );
+
+        let frontmatter_only = MermaidConfig::merge_layers(&site_config, &frontmatter, &json!({}));
+        assert_eq!(frontmatter_only.get_str("theme"), Some("forest"));
+    }
+
+    #[test]
+    fn merge_layers_ignores_non_object_layers() {
+        let base = MermaidConfig::from_value(json!({ "theme": "dark" }));
+
+        let merged = MermaidConfig::merge_layers(&base, &Value::Null, &json!("base"));
+
+        assert_eq!(merged, base);
+    }
 }
@@ -44,6 +44,10 @@ impl DetectorRegistry {
         }
     }
 
+    pub(crate) fn profile(&self) -> BaselineRegistryProfile {
+        self.profile
+    }
+
     /// Adds a detector entry to the end of the ordered registry.
     pub fn add(&mut self, detector: Detector) {
         self.detectors.push(detector);
@@ -4,13 +4,19 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
+use std::ops::Range;
 
 pub const BLOCK_WIDTH_WARNING_RULE_ID: &str = "merman.block.width_exceeds_columns";
+pub const ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID: &str =
+    "merman.semantic.er.unrecognized_attribute_type";
 pub const FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID: &str =
     "merman.authoring.flowchart.explicit_direction";
 pub const FLOWCHART_UNKNOWN_STYLE_TARGET_WARNING_RULE_ID: &str =
     "merman.semantic.flowchart.unknown_style_target";
 pub const GIT_GRAPH_DUPLICATE_COMMIT_WARNING_RULE_ID: &str = "merman.git_graph.duplicate_commit_id";
+pub const SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID: &str =
+    "merman.semantic.sequence.invalid_actor_json";
 
 /// Shared warning fact emitted by diagram families for analysis and lint consumers.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -140,6 +146,26 @@ pub struct ParsedDiagram {
     pub meta: ParseMetadata,
     /// Semantic JSON model matching Mermaid's parser/database output shape where possible.
     pub model: Value,
+    /// Source ranges for model elements, present only when
+    /// [`crate::ParseOptions::include_spans`] is set.
+    pub spans: Option<DiagramSpans>,
+}
+
+/// Byte ranges in the original source text for elements of a [`ParsedDiagram::model`].
+///
+/// Flowcharts fill `nodes` and `edges`; sequence diagrams fill `messages`. Other diagram types
+/// return an empty map set. Elements whose position cannot be mapped back through preprocessing
+/// are left out rather than reported in parser-input coordinates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiagramSpans {
+    /// Node id to the range of the id where the node first appears.
+    pub nodes: BTreeMap<String, Range<usize>>,
+    /// Edge id (as in `model.edges[].id`) to the range of its link, from any edge id prefix
+    /// through the edge label.
+    pub edges: BTreeMap<String, Range<usize>>,
+    /// Index into `model.messages` to the range of the statement that produced the entry.
+    /// Block markers point at their `loop ...`/`else ...`/`end` line.
+    pub messages: BTreeMap<usize, Range<usize>>,
 }
 
 /// Parser-backed editor facts produced alongside a successful semantic JSON parse.
@@ -240,7 +266,9 @@ impl RenderSemanticModel {
             Self::Json(v) => Self::remap_json_warning_fact_spans(v, &mut remap),
             Self::Flowchart(v) => Self::remap_warning_fact_slice(&mut v.warning_facts, &mut remap),
             Self::Block(v) => Self::remap_warning_fact_slice(&mut v.warning_facts, &mut remap),
+            Self::Er(v) => Self::remap_warning_fact_slice(&mut v.warning_facts, &mut remap),
             Self::GitGraph(v) => Self::remap_warning_fact_slice(&mut v.warning_facts, &mut remap),
+            Self::Sequence(v) => Self::remap_warning_fact_slice(&mut v.warning_facts, &mut remap),
             _ => {}
         }
     }
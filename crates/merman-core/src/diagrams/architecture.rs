@@ -714,26 +714,10 @@ fn parse_acc_descr_block(
     if !t.starts_with("accDescr") {
         return None;
     }
-    let rest = t["accDescr".len()..].trim_start();
-    let rest = rest.strip_prefix('{')?;
-
-    let mut buf = String::new();
-    if let Some(end) = rest.find('}') {
-        buf.push_str(&rest[..end]);
-        return Some(buf.trim().to_string());
-    }
-    buf.push_str(rest);
-    buf.push('\n');
-
-    while let Some(line) = lines.next() {
-        if let Some(end) = line.text.find('}') {
-            buf.push_str(&line.text[..end]);
-            break;
-        }
-        buf.push_str(line.text);
-        buf.push('\n');
-    }
-    Some(buf.trim().to_string())
+    crate::common_db::collect_acc_descr_block(
+        &t["accDescr".len()..],
+        std::iter::from_fn(|| lines.next().map(|line| line.text)),
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -2157,6 +2141,7 @@ mod tests {
             config: MermaidConfig::default(),
             effective_config: MermaidConfig::default(),
             title: None,
+            warnings: Vec::new(),
         }
     }
 
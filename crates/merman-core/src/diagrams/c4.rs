@@ -157,6 +157,36 @@ pub struct C4RelRenderModel {
     pub text_color: Option<String>,
 }
 
+/// Routing hint carried by the directional `Rel_*` macros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum C4RelDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    Back,
+}
+
+impl C4RelRenderModel {
+    /// The routing hint of `Rel_U` / `Rel_D` / `Rel_L` / `Rel_R` / `Rel_Back` (and their long
+    /// forms); `None` for plain `Rel` and `BiRel`.
+    pub fn direction(&self) -> Option<C4RelDirection> {
+        match self.rel_type.as_str() {
+            "rel_u" => Some(C4RelDirection::Up),
+            "rel_d" => Some(C4RelDirection::Down),
+            "rel_l" => Some(C4RelDirection::Left),
+            "rel_r" => Some(C4RelDirection::Right),
+            "rel_b" => Some(C4RelDirection::Back),
+            _ => None,
+        }
+    }
+
+    /// Whether the relationship was declared with `BiRel`.
+    pub fn is_bidirectional(&self) -> bool {
+        self.rel_type == "birel"
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct C4DiagramRenderModel {
     #[serde(default, rename = "c4Type")]
@@ -2455,6 +2485,45 @@ Rel_Back(a, b, "l11")
         assert_eq!(model["rels"][0]["label"]["text"], json!("l11"));
     }
 
+    #[test]
+    fn c4_rel_render_model_exposes_direction_and_bidirectional_flag() {
+        let engine = Engine::new();
+        let input = r#"C4Context
+Rel(a, b, "plain")
+BiRel(a, c, "both")
+Rel_U(a, d, "up")
+Rel_Down(a, e, "down")
+Rel_L(a, f, "left")
+Rel_Right(a, g, "right")
+Rel_Back(a, h, "back")
+"#;
+        let parsed = engine
+            .parse_diagram_for_render_model_sync(input, ParseOptions::strict())
+            .unwrap()
+            .unwrap();
+        let RenderSemanticModel::C4(model) = parsed.model else {
+            panic!("c4 render parse should return typed model");
+        };
+
+        let flags = model
+            .rels
+            .iter()
+            .map(|r| (r.to_alias.as_str(), r.direction(), r.is_bidirectional()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            [
+                ("b", None, false),
+                ("c", None, true),
+                ("d", Some(C4RelDirection::Up), false),
+                ("e", Some(C4RelDirection::Down), false),
+                ("f", Some(C4RelDirection::Left), false),
+                ("g", Some(C4RelDirection::Right), false),
+                ("h", Some(C4RelDirection::Back), false),
+            ]
+        );
+    }
+
     #[test]
     fn c4_rel_without_label_is_ignored_like_mermaid_db() {
         let model = parse(
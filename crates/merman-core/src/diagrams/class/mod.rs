@@ -4,15 +4,10 @@ lalrpop_util::lalrpop_mod!(
     "/diagrams/class_grammar.rs"
 );
 
-pub(crate) const LINE_SOLID: i32 = 0;
-pub(crate) const LINE_DOTTED: i32 = 1;
-
-pub(crate) const REL_AGGREGATION: i32 = 0;
-pub(crate) const REL_EXTENSION: i32 = 1;
-pub(crate) const REL_COMPOSITION: i32 = 2;
-pub(crate) const REL_DEPENDENCY: i32 = 3;
-pub(crate) const REL_LOLLIPOP: i32 = 4;
-pub(crate) const REL_NONE: i32 = -1;
+pub(crate) use crate::models::class_diagram::{
+    LINE_DOTTED, LINE_SOLID, REL_AGGREGATION, REL_COMPOSITION, REL_DEPENDENCY, REL_EXTENSION,
+    REL_LOLLIPOP, REL_NONE,
+};
 
 pub(super) const MERMAID_DOM_ID_PREFIX: &str = "classId-";
 
@@ -7,6 +7,7 @@ fn meta() -> ParseMetadata {
         config: MermaidConfig::default(),
         effective_config: MermaidConfig::default(),
         title: None,
+        warnings: Vec::new(),
     }
 }
 
@@ -70,3 +71,67 @@ Platform.FFI.PythonBinding --> Platform.Core.Renderer : calls
         vec!["Renderer"]
     );
 }
+
+#[test]
+fn relation_shape_exposes_readable_line_and_marker_names() {
+    let code = r#"classDiagram
+A <|-- B
+C *-- D
+E o-- F
+G --> H
+I ..> J
+"#;
+    let model = parse::parse_class_typed(code, &meta()).expect("class diagram should parse");
+    let hints = model
+        .relations
+        .iter()
+        .map(|r| {
+            (
+                r.relation.line(),
+                r.relation.arrow_start(),
+                r.relation.arrow_end(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        hints,
+        vec![
+            ("solid", "triangle", "none"),
+            ("solid", "composition", "none"),
+            ("solid", "aggregation", "none"),
+            ("solid", "none", "arrow"),
+            ("dashed", "none", "arrow"),
+        ]
+    );
+    assert_eq!(model.relations[4].relation.line_type, LINE_DOTTED);
+    assert_eq!(model.relations[0].relation.type1, REL_EXTENSION);
+}
+
+#[test]
+fn class_members_expose_default_values_and_annotations() {
+    let code = r#"classDiagram
+class Counter {
+  +count: int = 0
+  +String name
+  +total$
+  {abstract} +run() void
+  +reset()* bool
+}
+"#;
+    let model = parse::parse_class_typed(code, &meta()).expect("class diagram should parse");
+    let counter = &model.classes["Counter"];
+
+    let count = &counter.members[0];
+    assert_eq!(count.display_text, "+count: int &#61; 0");
+    assert_eq!(count.default_value(), Some("0"));
+    assert!(count.annotations().is_empty());
+    assert_eq!(counter.members[1].default_value(), None);
+    assert_eq!(counter.members[2].annotations(), vec!["static"]);
+
+    let run = &counter.methods[0];
+    assert_eq!(run.annotations(), vec!["abstract"]);
+    assert_eq!(run.default_value(), None);
+    assert_eq!(run.return_type, "void");
+    assert_eq!(counter.methods[1].annotations(), vec!["abstract"]);
+}
@@ -1,3 +1,6 @@
+use crate::diagram::{
+    DiagramWarningFact, ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID, legacy_warning_messages,
+};
 use crate::{
     EditorExpectedSyntax, EditorExpectedSyntaxKind, EditorSemanticFacts, EditorSemanticKind,
     EditorSemanticSymbol, Error, ParseMetadata, Result, SourceSpan,
@@ -6,6 +9,10 @@ use crate::{
 use serde_json::{Value, json};
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
+fn is_false(v: &bool) -> bool {
+    !*v
+}
+
 lalrpop_util::lalrpop_mod!(
     #[allow(clippy::empty_line_after_outer_attr)]
     er_grammar,
@@ -25,6 +32,12 @@ pub struct ErDiagramRenderModel {
     pub entities: BTreeMap<String, ErEntityRenderModel>,
     #[serde(default)]
     pub relationships: Vec<ErRelationshipRenderModel>,
+    #[serde(
+        default,
+        rename = "warningFacts",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub warning_facts: Vec<DiagramWarningFact>,
 }
 
 impl ErDiagramRenderModel {
@@ -43,6 +56,13 @@ pub struct ErAttributeRenderModel {
     pub keys: Vec<String>,
     #[serde(default)]
     pub comment: String,
+    /// Derived from `keys` so renderers don't have to scan the token list.
+    #[serde(default, rename = "isPrimaryKey", skip_serializing_if = "is_false")]
+    pub is_primary_key: bool,
+    #[serde(default, rename = "isForeignKey", skip_serializing_if = "is_false")]
+    pub is_foreign_key: bool,
+    #[serde(default, rename = "isUniqueKey", skip_serializing_if = "is_false")]
+    pub is_unique_key: bool,
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -65,6 +85,9 @@ pub struct ErRelationshipRenderModel {
     pub entity_b: String,
     #[serde(default, rename = "relSpec")]
     pub rel_spec: ErRelSpecRenderModel,
+    /// Set when both endpoints are the same entity, so layout can route a loop.
+    #[serde(default, rename = "isSelf", skip_serializing_if = "is_false")]
+    pub is_self: bool,
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -163,6 +186,7 @@ struct ErDb {
     entity_counter: usize,
     acc_title: Option<String>,
     acc_descr: Option<String>,
+    warning_facts: Vec<DiagramWarningFact>,
 }
 
 impl ErDb {
@@ -205,7 +229,10 @@ impl ErDb {
         let Some(e) = self.entities.get_mut(entity) else {
             return;
         };
-        for a in attributes {
+        for mut a in attributes {
+            a.is_primary_key = a.keys.iter().any(|k| k == "PK");
+            a.is_foreign_key = a.keys.iter().any(|k| k == "FK");
+            a.is_unique_key = a.keys.iter().any(|k| k == "UK");
             e.attributes.push(a);
         }
     }
@@ -219,6 +246,7 @@ impl ErDb {
             role_a: role.to_string(),
             entity_b: entity_b.id.clone(),
             rel_spec: spec,
+            is_self: entity_a.id == entity_b.id,
         });
     }
 
@@ -312,10 +340,12 @@ impl ErDb {
             classes: self.classes.into_iter().collect(),
             entities: self.entities.into_iter().collect(),
             relationships: self.relationships,
+            warning_facts: self.warning_facts,
         }
     }
 
     fn into_model(self, meta: &ParseMetadata) -> Result<Value> {
+        let warnings = legacy_warning_messages(&self.warning_facts);
         let mut value = serde_json::to_value(self.into_render_model())
             .map_err(|e| Error::diagram_parse_fallback(meta.diagram_type.clone(), e.to_string()))?;
         let Value::Object(obj) = &mut value else {
@@ -323,6 +353,9 @@ impl ErDb {
         };
 
         obj.insert("type".to_string(), json!(meta.diagram_type));
+        if !warnings.is_empty() {
+            obj.insert("warnings".to_string(), json!(warnings));
+        }
         obj.insert(
             "constants".to_string(),
             json!({
@@ -364,13 +397,97 @@ fn parse_er_db(code: &str, meta: &ParseMetadata) -> Result<ErDb> {
             )
         })?;
 
+    let validate_attribute_types = meta
+        .effective_config
+        .get_bool("er.validateAttributeTypes")
+        .unwrap_or(false);
+
     let mut db = ErDb::new();
     for a in actions {
+        if validate_attribute_types && let Action::AddAttributes { entity, attributes } = &a {
+            db.warning_facts
+                .extend(unrecognized_attribute_type_warnings(entity, attributes));
+        }
         db.apply(a);
     }
+    // Relationship labels reach the SVG as text, so run them through the same
+    // security-level-aware sanitization as titles and state edge labels.
+    for rel in &mut db.relationships {
+        rel.role_a = crate::sanitize::sanitize_text(&rel.role_a, &meta.effective_config);
+    }
     Ok(db)
 }
 
+/// Opt-in lint (`er.validateAttributeTypes`) for attribute types outside the usual SQL-ish shapes.
+///
+/// Mermaid accepts any attribute word as a type, so this only reports warnings and never changes
+/// the parsed model.
+fn unrecognized_attribute_type_warnings(
+    entity: &str,
+    attributes: &[Attribute],
+) -> Vec<DiagramWarningFact> {
+    attributes
+        .iter()
+        .filter(|attr| !is_recognized_attribute_type(&attr.ty))
+        .map(|attr| {
+            DiagramWarningFact::new(
+                ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID,
+                format!(
+                    "attribute `{}` on entity `{entity}` has unrecognized type `{}`",
+                    attr.name, attr.ty
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Accepts identifiers with optional generics (`list~T~`), a length (`varchar(10)`,
+/// `decimal(10,2)`), and trailing array markers (`int[]`, `int[4]`).
+fn is_recognized_attribute_type(ty: &str) -> bool {
+    let mut rest = ty;
+    while let Some(inner) = rest.strip_suffix(']') {
+        let Some((head, size)) = inner.rsplit_once('[') else {
+            return false;
+        };
+        if !size.chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        rest = head;
+    }
+
+    if let Some(inner) = rest.strip_suffix(')') {
+        let Some((head, length)) = inner.split_once('(') else {
+            return false;
+        };
+        let length_ok = length
+            .split(',')
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if !length_ok {
+            return false;
+        }
+        rest = head;
+    }
+
+    if let Some((name, generic)) = rest.split_once('~') {
+        let Some(generic) = generic.strip_suffix('~') else {
+            return false;
+        };
+        return is_attribute_type_identifier(name)
+            && generic.split(',').all(is_recognized_attribute_type);
+    }
+
+    is_attribute_type_identifier(rest)
+}
+
+fn is_attribute_type_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    (first == '_' || first.is_alphabetic())
+        && chars.all(|c| c == '_' || c == '-' || c.is_alphanumeric())
+}
+
 pub fn parse_er_model_for_render(code: &str, meta: &ParseMetadata) -> Result<ErDiagramRenderModel> {
     let db = parse_er_db(code, meta)?;
     Ok(db.into_render_model())
@@ -873,8 +990,12 @@ impl<'input> Lexer<'input> {
                 self.pos,
             )));
         }
-        let colon_pos = rest.find(':')?;
-        self.pos = after + colon_pos + 1;
+        // Mermaid's `accDescr\s*":"` rule: only whitespace may separate the keyword and colon.
+        if !rest_trim.starts_with(':') {
+            return None;
+        }
+        let consumed_ws = rest.len() - rest_trim.len();
+        self.pos = after + consumed_ws + 1;
         let s = self.read_to_newline();
         Some(Ok((start, Tok::AccDescr(s.trim().to_string()), self.pos)))
     }
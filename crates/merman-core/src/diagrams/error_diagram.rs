@@ -10,7 +10,11 @@ pub fn parse_error(_code: &str, meta: &ParseMetadata) -> Result<Value> {
 
 pub(crate) fn suppressed_error_diagram(source_meta: &ParseMetadata) -> ParsedDiagram {
     let (meta, model) = suppressed_error_parts(source_meta);
-    ParsedDiagram { meta, model }
+    ParsedDiagram {
+        meta,
+        model,
+        spans: None,
+    }
 }
 
 pub(crate) fn suppressed_error_render_diagram(source_meta: &ParseMetadata) -> ParsedDiagramRender {
@@ -573,6 +573,7 @@ mod tests {
             config: MermaidConfig::empty_object(),
             effective_config: MermaidConfig::empty_object(),
             title: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -1,10 +1,10 @@
 use crate::diagram::legacy_warning_messages;
 use crate::sanitize::sanitize_text;
 use crate::{
-    DiagramWarningFact, EditorCompletionDialect, EditorExpectedSyntax, EditorExpectedSyntaxKind,
-    EditorRenameDomain, EditorSemanticFacts, EditorSemanticKind, EditorSemanticRole,
-    EditorSemanticSymbol, Error, FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID, MermaidConfig,
-    ParseMetadata, Result, SourceSpan,
+    DiagramSpans, DiagramWarningFact, EditorCompletionDialect, EditorExpectedSyntax,
+    EditorExpectedSyntaxKind, EditorRenameDomain, EditorSemanticFacts, EditorSemanticKind,
+    EditorSemanticRole, EditorSemanticSymbol, Error, FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID,
+    MermaidConfig, ParseMetadata, Result, SourceSpan,
     editor::{format_lalrpop_parse_error, lalrpop_parse_diagnostic, lalrpop_recovery_span},
 };
 use indexmap::IndexMap;
@@ -39,7 +39,9 @@ use text::{
     parse_edge_label_text, parse_label_text, strip_wrapping_backticks, title_kind_str, unquote,
 };
 
-pub use model::{FlowEdge, FlowEdgeDefaults, FlowNode, FlowSubgraph, FlowchartV2Model};
+pub use model::{
+    FlowCallback, FlowEdge, FlowEdgeDefaults, FlowNode, FlowSubgraph, FlowchartV2Model,
+};
 
 pub(crate) use model::{
     Edge, EdgeDefaults, LabeledText, LinkToken, Node, SubgraphHeader, TitleKind,
@@ -58,8 +60,8 @@ use lexer::Lexer;
 use link::{destruct_end_link, destruct_start_link};
 use semantic::{FlowchartSemanticContext, apply_semantic_statements};
 use shape_data::{
-    apply_shape_data_to_node, parse_shape_data, public_shape_names_11_12_2, value_to_bool,
-    value_to_string,
+    apply_shape_data_to_node, canonical_shape_11_12_2, parse_shape_data,
+    public_shape_names_11_12_2, value_to_bool, value_to_string,
 };
 use subgraph::SubgraphBuilder;
 
@@ -91,6 +93,7 @@ struct FlowchartSemanticSource {
     acc_descr: Option<String>,
     class_defs: IndexMap<String, Vec<String>>,
     tooltips: HashMap<String, String>,
+    callbacks: HashMap<String, FlowCallback>,
     edge_defaults: EdgeDefaults,
     vertex_calls: Vec<String>,
     nodes: Vec<Node>,
@@ -119,6 +122,20 @@ pub(crate) fn parse_flowchart_json_and_editor_facts(
     Ok((model, facts))
 }
 
+/// Parses the semantic JSON model and, from the same AST, the node and edge spans in parser-input
+/// coordinates.
+pub(crate) fn parse_flowchart_json_and_spans(
+    code: &str,
+    meta: &ParseMetadata,
+) -> Result<(Value, DiagramSpans)> {
+    let source = parse_flowchart_semantic_source(code, meta)?;
+    let spans = source.spans(code);
+    Ok((
+        source.into_compat_json(&meta.diagram_type, &meta.effective_config),
+        spans,
+    ))
+}
+
 pub fn parse_flowchart_model_for_render(
     code: &str,
     meta: &ParseMetadata,
@@ -130,6 +147,12 @@ pub fn flowchart_public_shape_names() -> impl Iterator<Item = &'static str> {
     public_shape_names_11_12_2()
 }
 
+/// Maps a public flowchart shape name or alias (e.g. `database`, `process`) to Mermaid's
+/// canonical short name (`cyl`, `rect`). Returns `None` for names shapeData would reject.
+pub fn canonical_shape(name: &str) -> Option<&'static str> {
+    canonical_shape_11_12_2(name)
+}
+
 pub fn parse_flowchart_editor_facts(
     code: &str,
     _meta: &ParseMetadata,
@@ -182,7 +205,9 @@ fn parse_flowchart_semantic_source_from_ast(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
     let mut builder = SubgraphBuilder::new(inherit_dir, ast.direction.clone());
-    builder.visit_statements(&ast.statements);
+    builder
+        .visit_statements(&ast.statements)
+        .map_err(|e| Error::diagram_parse_fallback(meta.diagram_type.clone(), e))?;
 
     let subgraph_ids: HashSet<String> = builder
         .subgraphs
@@ -206,6 +231,7 @@ fn parse_flowchart_semantic_source_from_ast(
 
     let mut class_defs: IndexMap<String, Vec<String>> = IndexMap::new();
     let mut tooltips: HashMap<String, String> = HashMap::new();
+    let mut callbacks: HashMap<String, FlowCallback> = HashMap::new();
     let mut edge_defaults = EdgeDefaults {
         style: Vec::new(),
         interpolate: None,
@@ -230,6 +256,7 @@ fn parse_flowchart_semantic_source_from_ast(
             subgraph_index: &mut subgraph_index,
             class_defs: &mut class_defs,
             tooltips: &mut tooltips,
+            callbacks: &mut callbacks,
             edge_defaults: &mut edge_defaults,
             security_level_loose,
             diagram_type: &meta.diagram_type,
@@ -251,6 +278,7 @@ fn parse_flowchart_semantic_source_from_ast(
         acc_title,
         class_defs,
         tooltips,
+        callbacks,
         edge_defaults,
         vertex_calls,
         nodes,
@@ -653,7 +681,7 @@ fn collect_editor_facts_from_statements_with_seen_edges(
 ) {
     for stmt in statements {
         match stmt {
-            Stmt::Chain { nodes, edges } => {
+            Stmt::Chain { nodes, edges, .. } => {
                 for node in nodes {
                     push_flowchart_node_symbol(facts, node);
                 }
@@ -1160,6 +1188,26 @@ fn push_flowchart_token_symbol(
 }
 
 impl FlowchartSemanticSource {
+    fn spans(&self, code: &str) -> DiagramSpans {
+        let mut spans = DiagramSpans::default();
+        for node in &self.nodes {
+            if let Some(span) = node.id_span {
+                spans.nodes.insert(node.id.clone(), span.start..span.end);
+            }
+        }
+        for edge in &self.edges {
+            let (Some(id), Some(span)) = (&edge.id, edge.span) else {
+                continue;
+            };
+            // Arrow tokens carry the whitespace around them; report just the link text.
+            let text = code.get(span.start..span.end).unwrap_or_default();
+            let start = span.start + (text.len() - text.trim_start().len());
+            let end = span.start + text.trim_end().len();
+            spans.edges.insert(id.clone(), start..end.max(start));
+        }
+        spans
+    }
+
     fn into_compat_json(self, diagram_type: &str, config: &MermaidConfig) -> Value {
         let FlowchartSemanticSource {
             keyword,
@@ -1168,6 +1216,7 @@ impl FlowchartSemanticSource {
             acc_descr,
             class_defs,
             tooltips,
+            mut callbacks,
             edge_defaults,
             vertex_calls,
             mut nodes,
@@ -1196,7 +1245,10 @@ impl FlowchartSemanticSource {
             "vertexCalls": vertex_calls,
             "nodes": nodes
                 .into_iter()
-                .map(|node| flow_node_to_json(node, config))
+                .map(|node| {
+                    let callback = callbacks.remove(&node.id);
+                    flow_node_to_json(node, callback, config)
+                })
                 .collect::<Vec<_>>(),
             "edges": edges
                 .into_iter()
@@ -1231,6 +1283,7 @@ impl FlowchartSemanticSource {
             warning_facts,
             effective_direction,
             tooltips,
+            mut callbacks,
             keyword: _,
         } = self;
 
@@ -1238,6 +1291,14 @@ impl FlowchartSemanticSource {
             append_missing_subgraph_nodes(&mut nodes, &subgraphs);
         }
 
+        let nodes = nodes
+            .into_iter()
+            .map(|node| {
+                let callback = callbacks.remove(&node.id);
+                flow_node_to_model(node, callback, &class_defs, &meta.effective_config)
+            })
+            .collect::<Vec<_>>();
+
         Ok(FlowchartV2Model {
             acc_descr,
             acc_title,
@@ -1248,10 +1309,7 @@ impl FlowchartSemanticSource {
                 interpolate: edge_defaults.interpolate,
             }),
             vertex_calls,
-            nodes: nodes
-                .into_iter()
-                .map(|node| flow_node_to_model(node, &meta.effective_config))
-                .collect::<Vec<_>>(),
+            nodes,
             edges: edges
                 .into_iter()
                 .map(|edge| flow_edge_to_model(edge, meta))
@@ -1296,11 +1354,11 @@ fn append_missing_subgraph_nodes(nodes: &mut Vec<Node>, subgraphs: &[FlowSubGrap
     }
 }
 
-fn flow_node_to_json(n: Node, config: &MermaidConfig) -> Value {
+fn flow_node_to_json(n: Node, callback: Option<FlowCallback>, config: &MermaidConfig) -> Value {
     let layout_shape = layout_shape_for_node(&n);
     let label = sanitized_node_label(&n, config);
 
-    json!({
+    let mut node = json!({
         "id": n.id,
         "label": label,
         "labelType": title_kind_str(&n.label_type),
@@ -1318,12 +1376,23 @@ fn flow_node_to_json(n: Node, config: &MermaidConfig) -> Value {
         "link": n.link,
         "linkTarget": n.link_target,
         "haveCallback": n.have_callback,
-    })
+    });
+    if let Some(callback) = callback {
+        node["callback"] = json!(callback);
+        node["callbackEffective"] = json!(callback_effective(config));
+    }
+    node
 }
 
-fn flow_node_to_model(n: Node, config: &MermaidConfig) -> FlowNode {
+fn flow_node_to_model(
+    n: Node,
+    callback: Option<FlowCallback>,
+    class_defs: &IndexMap<String, Vec<String>>,
+    config: &MermaidConfig,
+) -> FlowNode {
     let layout_shape = layout_shape_for_node(&n);
     let label = sanitized_node_label(&n, config);
+    let resolved_styles = resolved_node_styles(&n, class_defs);
 
     FlowNode {
         id: n.id,
@@ -1339,12 +1408,60 @@ fn flow_node_to_model(n: Node, config: &MermaidConfig) -> FlowNode {
         asset_height: n.asset_height,
         classes: n.classes,
         styles: n.styles,
+        resolved_styles,
+        link_target_effective: effective_link_target(
+            n.link.as_deref(),
+            n.link_target.as_deref(),
+            config,
+        ),
         link: n.link,
         link_target: n.link_target,
         have_callback: n.have_callback,
+        callback_effective: callback.is_some() && callback_effective(config),
+        callback,
     }
 }
 
+/// Mirrors FlowDB `addNodeFromVertex`: `getCompiledStyles(['default', 'node', ...classes])`
+/// followed by the node's own `style` declarations.
+///
+/// Like FlowDB `addClass`, every class declaration containing `color` is repeated as a text style
+/// with its first `fill` renamed to `bgFill`, right after that class's styles.
+fn resolved_node_styles(n: &Node, class_defs: &IndexMap<String, Vec<String>>) -> Vec<String> {
+    let mut out = Vec::new();
+    let class_names = ["default", "node"]
+        .into_iter()
+        .chain(n.classes.iter().map(String::as_str));
+    for class in class_names {
+        let Some(styles) = class_defs.get(class) else {
+            continue;
+        };
+        out.extend(styles.iter().map(|s| s.trim().to_string()));
+        out.extend(model::class_text_styles(styles));
+    }
+    out.extend(n.styles.iter().map(|s| s.trim().to_string()));
+    out
+}
+
+fn effective_link_target(
+    link: Option<&str>,
+    target: Option<&str>,
+    config: &MermaidConfig,
+) -> Option<String> {
+    // Mermaid's node renderer forces `_top` under `securityLevel: sandbox` and otherwise emits
+    // the explicit target only (no attribute, i.e. the browser's `_self`, when omitted).
+    link?;
+    if config.get_str("securityLevel") == Some("sandbox") {
+        return Some("_top".to_string());
+    }
+    target.map(str::to_string)
+}
+
+fn callback_effective(config: &MermaidConfig) -> bool {
+    // Mermaid only binds click callbacks when `securityLevel` is `loose`.
+    config.get_str("securityLevel") == Some("loose")
+}
+
 fn flow_edge_to_json(e: Edge, config: &MermaidConfig) -> Value {
     let label = sanitized_optional_label(e.label.as_deref(), config);
 
@@ -1395,6 +1512,15 @@ fn flow_edge_to_model(e: Edge, meta: &ParseMetadata) -> Result<FlowEdge> {
 
 fn layout_shape_for_node(n: &Node) -> String {
     // Mirrors Mermaid FlowDB `getTypeFromVertex` logic at 11.12.2.
+    //
+    // `shape` is the vertex `type`: the bracket syntax name (`square`, `round`, `circle`, ...) or
+    // the `@{ shape: ... }` value verbatim, with later declarations overriding earlier ones.
+    // `layoutShape` is derived from it:
+    //   img set                -> imageSquare
+    //   icon set               -> iconCircle / iconSquare / iconRounded / icon (by `form`)
+    //   square or no shape     -> squareRect
+    //   round                  -> roundedRect
+    //   anything else          -> same as `shape` (e.g. ellipse, circle, rounded, hex)
     if n.img.is_some() {
         return "imageSquare".to_string();
     }
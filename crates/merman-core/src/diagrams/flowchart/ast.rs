@@ -29,7 +29,10 @@ pub(crate) struct ClassAssignStmt {
 
 #[derive(Debug, Clone)]
 pub(crate) enum ClickAction {
-    Callback,
+    Callback {
+        function: String,
+        args: Option<String>,
+    },
     Link {
         href: String,
         target: Option<String>,
@@ -75,6 +78,8 @@ pub(crate) enum Stmt {
     Chain {
         nodes: Vec<Node>,
         edges: Vec<Edge>,
+        /// Lengths of the `&`-separated node groups that make up `nodes`, in source order.
+        group_sizes: Vec<usize>,
     },
     Node(Box<Node>),
     Subgraph(SubgraphBlock),
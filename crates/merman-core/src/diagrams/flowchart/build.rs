@@ -41,7 +41,7 @@ impl FlowchartBuildState {
             };
 
             match stmt {
-                Stmt::Chain { nodes, edges } => {
+                Stmt::Chain { nodes, edges, .. } => {
                     let mut deferred_shape_data_vertex_calls: Vec<String> = Vec::new();
                     for mut n in nodes.iter().cloned() {
                         // Mermaid FlowDB `vertexCounter` increments on every `addVertex(...)` call.
@@ -384,6 +384,22 @@ impl<'a> ClickParse<'a> {
     }
 }
 
+/// Link targets Mermaid's `LINK_TARGET` token accepts after a click link.
+const LINK_TARGETS: &[&str] = &["_self", "_blank", "_parent", "_top"];
+
+fn take_link_target(p: &mut ClickParse<'_>) -> std::result::Result<Option<String>, LexError> {
+    let Some(word) = p.take_word().filter(|w| w.starts_with('_')) else {
+        return Ok(None);
+    };
+    if !LINK_TARGETS.contains(&word.as_str()) {
+        return Err(LexError::new(format!(
+            "Invalid click statement: unknown link target {word} (expected one of {})",
+            LINK_TARGETS.join(", ")
+        )));
+    }
+    Ok(Some(word))
+}
+
 pub(super) fn parse_click_stmt(rest: &str) -> std::result::Result<ClickStmt, LexError> {
     let mut p = ClickParse::new(rest);
     let Some(id) = p.take_word() else {
@@ -406,7 +422,7 @@ pub(super) fn parse_click_stmt(rest: &str) -> std::result::Result<ClickStmt, Lex
             return Err(LexError::new("Invalid click statement".to_string()));
         };
         let maybe_tt = p.take_quoted();
-        let maybe_target = p.take_word().filter(|w| w.starts_with('_'));
+        let maybe_target = take_link_target(&mut p)?;
         tooltip = maybe_tt;
         action = ClickAction::Link {
             href: link,
@@ -438,19 +454,26 @@ pub(super) fn parse_click_stmt(rest: &str) -> std::result::Result<ClickStmt, Lex
         if p.i == start {
             return Err(LexError::new("Invalid click statement".to_string()));
         }
+        let function = p.s[start..p.i].to_string();
         p.skip_ws();
+        let mut args: Option<String> = None;
         if p.peek() == Some(b'(') {
             p.i += 1;
+            let args_start = p.i;
             while p.i < p.s.len() && p.s.as_bytes()[p.i] != b')' {
                 p.i += 1;
             }
+            // Mermaid's `CALLBACKARGS` token only exists for a non-empty `(...)` body.
+            if p.i > args_start {
+                args = Some(p.s[args_start..p.i].to_string());
+            }
             if p.peek() == Some(b')') {
                 p.i += 1;
             }
         }
 
         tooltip = p.take_quoted();
-        action = ClickAction::Callback;
+        action = ClickAction::Callback { function, args };
         return Ok(ClickStmt {
             ids,
             tooltip,
@@ -460,7 +483,7 @@ pub(super) fn parse_click_stmt(rest: &str) -> std::result::Result<ClickStmt, Lex
 
     if let Some(link) = p.take_quoted() {
         let maybe_tt = p.take_quoted();
-        let maybe_target = p.take_word().filter(|w| w.starts_with('_'));
+        let maybe_target = take_link_target(&mut p)?;
         tooltip = maybe_tt;
         action = ClickAction::Link {
             href: link,
@@ -473,11 +496,14 @@ pub(super) fn parse_click_stmt(rest: &str) -> std::result::Result<ClickStmt, Lex
         });
     }
 
-    let Some(_function_name) = p.take_word() else {
+    let Some(function) = p.take_word() else {
         return Err(LexError::new("Invalid click statement".to_string()));
     };
     tooltip = p.take_quoted();
-    action = ClickAction::Callback;
+    action = ClickAction::Callback {
+        function,
+        args: None,
+    };
     Ok(ClickStmt {
         ids,
         tooltip,
@@ -541,7 +567,10 @@ mod tests {
         assert_eq!(stmt.ids, vec!["A"]);
         assert!(stmt.tooltip.is_none());
         match stmt.action {
-            ClickAction::Callback => {}
+            ClickAction::Callback { function, args } => {
+                assert_eq!(function, "callback");
+                assert!(args.is_none());
+            }
             _ => panic!("expected callback action"),
         }
     }
@@ -552,7 +581,10 @@ mod tests {
         assert_eq!(stmt.ids, vec!["A"]);
         assert!(stmt.tooltip.is_none());
         match stmt.action {
-            ClickAction::Callback => {}
+            ClickAction::Callback { function, args } => {
+                assert_eq!(function, "callback");
+                assert!(args.is_none());
+            }
             _ => panic!("expected callback action"),
         }
     }
@@ -561,7 +593,10 @@ mod tests {
     fn parse_click_stmt_parses_call_callback_with_args() {
         let stmt = parse_click_stmt("A call callback(\"test0\", test1, test2)").unwrap();
         match stmt.action {
-            ClickAction::Callback => {}
+            ClickAction::Callback { function, args } => {
+                assert_eq!(function, "callback");
+                assert_eq!(args.as_deref(), Some("\"test0\", test1, test2"));
+            }
             _ => panic!("expected callback action"),
         }
     }
@@ -574,10 +574,13 @@ impl<'input> Lexer<'input> {
             return None;
         }
         let first = bytes[start];
-        if !first.is_ascii_alphanumeric() && first != b'_' {
+        if first.is_ascii_alphanumeric() || first == b'_' {
+            self.pos += 1;
+        } else if let Some(len) = unicode_letter_len(&self.input[start..]) {
+            self.pos += len;
+        } else {
             return None;
         }
-        self.pos += 1;
 
         while self.pos < bytes.len() {
             if self.pos + 1 < bytes.len()
@@ -613,6 +616,10 @@ impl<'input> Lexer<'input> {
                 self.pos += 1;
                 continue;
             }
+            if let Some(len) = unicode_letter_len(&self.input[self.pos..]) {
+                self.pos += len;
+                continue;
+            }
             break;
         }
 
@@ -1354,3 +1361,10 @@ fn trimmed_slice_with_span(input: &str, start: usize, end: usize) -> (&str, Sour
         SourceSpan::new(start, start + trimmed_len),
     )
 }
+
+/// Byte length of a leading non-ASCII letter, which Mermaid's `UNICODE_TEXT` token accepts in
+/// node ids (CJK, accented Latin, ...). Symbols such as emoji are not letters and stay invalid.
+fn unicode_letter_len(s: &str) -> Option<usize> {
+    let ch = s.chars().next()?;
+    (!ch.is_ascii() && ch.is_alphabetic()).then(|| ch.len_utf8())
+}
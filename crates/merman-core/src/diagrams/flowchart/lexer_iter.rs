@@ -116,6 +116,22 @@ impl<'input> Iterator for Lexer<'input> {
             return Some(Ok(tok));
         }
 
+        if let Some(ch) = self.input[start..]
+            .chars()
+            .next()
+            .filter(|ch| !ch.is_ascii())
+        {
+            // Mermaid only accepts Unicode letters in ids; other symbols (e.g. emoji) must be
+            // quoted label text.
+            self.pos += ch.len_utf8();
+            return Some(Err(LexError::with_span(
+                format!(
+                    "Unexpected character '{ch}' at {start}; node ids only accept letters, digits and '_', so quote it in a label (e.g. id[\"{ch}\"])"
+                ),
+                crate::SourceSpan::new(start, self.pos),
+            )));
+        }
+
         // Skip unknown single byte to avoid infinite loops.
         let _ = self.bump();
         Some(Err(LexError::with_span(
@@ -36,6 +36,23 @@ impl FlowchartV2Model {
         crate::common_db::sanitize_optional_acc_title(&mut self.acc_title, config);
         crate::common_db::sanitize_optional_acc_descr(&mut self.acc_descr, config);
     }
+
+    /// Text styles of `classDef id`, derived like FlowDB `addClass` (and the ER/class/state
+    /// `textStyles`): every declaration containing `color`, with its first `fill` renamed to
+    /// `bgFill`. `classDefs` itself keeps Mermaid's plain style lists.
+    pub fn class_def_text_styles(&self, id: &str) -> Vec<String> {
+        self.class_defs
+            .get(id)
+            .map(|styles| class_text_styles(styles).collect())
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn class_text_styles(styles: &[String]) -> impl Iterator<Item = String> + '_ {
+    styles
+        .iter()
+        .filter(|s| s.contains("color"))
+        .map(|s| s.replacen("fill", "bgFill", 1).trim().to_string())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +69,9 @@ pub struct FlowNode {
     pub label: Option<String>,
     #[serde(default, rename = "labelType")]
     pub label_type: Option<String>,
+    /// Renderer shape name derived from the vertex type as in Mermaid's `getTypeFromVertex`:
+    /// `square` (and untyped nodes) become `squareRect`, `round` becomes `roundedRect`, and
+    /// every other bracket or `@{ shape }` name passes through unchanged.
     #[serde(rename = "layoutShape")]
     pub layout_shape: Option<String>,
     #[serde(default)]
@@ -72,12 +92,41 @@ pub struct FlowNode {
     pub classes: Vec<String>,
     #[serde(default)]
     pub styles: Vec<String>,
+    /// `classDef` styles of `default`, `node` and [`Self::classes`] followed by [`Self::styles`],
+    /// in the order Mermaid emits them (`cssCompiledStyles` then `cssStyles`), so later entries
+    /// win. `classes` and `styles` stay the raw inputs.
+    #[serde(
+        default,
+        rename = "resolvedStyles",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub resolved_styles: Vec<String>,
     #[serde(default)]
     pub link: Option<String>,
+    /// Click target as authored, like FlowDB's `linkTarget`.
     #[serde(default, rename = "linkTarget")]
     pub link_target: Option<String>,
+    /// Effective `<a target>` for `link`: `_top` under `securityLevel: sandbox`, otherwise
+    /// [`Self::link_target`].
+    #[serde(default, rename = "linkTargetEffective")]
+    pub link_target_effective: Option<String>,
     #[serde(default, rename = "haveCallback")]
     pub have_callback: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback: Option<FlowCallback>,
+    #[serde(default, rename = "callbackEffective")]
+    pub callback_effective: bool,
+}
+
+/// Callback registered by `click <id> call fn(args)` / `click <id> fn`.
+///
+/// `args` follows Mermaid FlowDB `setClickFun`: arguments are split on commas outside double
+/// quotes, wrapping quotes are stripped, and the node id is used when no arguments are given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowCallback {
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +141,9 @@ pub struct FlowEdge {
     pub edge_type: Option<String>,
     #[serde(default)]
     pub stroke: Option<String>,
+    /// Curve set by `linkStyle N interpolate` or `@{curve: ...}`. Like FlowDB, both write this
+    /// one field in statement order, so the later statement wins. `None` falls back to
+    /// `edgeDefaults.interpolate` (`linkStyle default interpolate`) and then to config.
     #[serde(default)]
     pub interpolate: Option<String>,
     #[serde(default)]
@@ -155,6 +207,7 @@ pub(crate) struct Edge {
     pub label_type: TitleKind,
     pub label_span: Option<SourceSpan>,
     pub label_selection: Option<SourceSpan>,
+    pub span: Option<SourceSpan>,
     pub style: Vec<String>,
     pub classes: Vec<String>,
     pub interpolate: Option<String>,
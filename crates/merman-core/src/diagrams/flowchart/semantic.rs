@@ -5,10 +5,28 @@ use indexmap::IndexMap;
 use std::collections::HashMap;
 
 use super::{
-    ClickAction, Edge, EdgeDefaults, FlowSubGraph, LinkStylePos, Node, Stmt, TitleKind,
-    apply_shape_data_to_node, parse_shape_data, value_to_bool, value_to_string,
+    ClickAction, Edge, EdgeDefaults, FlowCallback, FlowSubGraph, LinkStylePos, Node, Stmt,
+    TitleKind, apply_shape_data_to_node, parse_shape_data, value_to_bool, value_to_string,
 };
 
+/// Curve names Mermaid's edge renderer (`rendering-util/rendering-elements/edges.js`) knows for
+/// `@{curve: ...}` edge metadata.
+const EDGE_CURVES: &[&str] = &[
+    "basis",
+    "bumpX",
+    "bumpY",
+    "cardinal",
+    "catmullRom",
+    "linear",
+    "monotoneX",
+    "monotoneY",
+    "natural",
+    "rounded",
+    "step",
+    "stepAfter",
+    "stepBefore",
+];
+
 pub(super) struct FlowchartSemanticContext<'a> {
     pub(super) nodes: &'a mut Vec<Node>,
     pub(super) node_index: &'a mut HashMap<String, usize>,
@@ -17,6 +35,7 @@ pub(super) struct FlowchartSemanticContext<'a> {
     pub(super) subgraph_index: &'a mut HashMap<String, usize>,
     pub(super) class_defs: &'a mut IndexMap<String, Vec<String>>,
     pub(super) tooltips: &'a mut HashMap<String, String>,
+    pub(super) callbacks: &'a mut HashMap<String, FlowCallback>,
     pub(super) edge_defaults: &'a mut EdgeDefaults,
     pub(super) security_level_loose: bool,
     pub(super) diagram_type: &'a str,
@@ -76,11 +95,18 @@ impl<'a> FlowchartSemanticContext<'a> {
                                     self.nodes[idx].link_target = target.clone();
                                 }
                             }
-                            ClickAction::Callback => {
-                                if self.security_level_loose
-                                    && let Some(&idx) = self.node_index.get(id)
-                                {
-                                    self.nodes[idx].have_callback = true;
+                            ClickAction::Callback { function, args } => {
+                                if let Some(&idx) = self.node_index.get(id) {
+                                    self.callbacks.insert(
+                                        id.clone(),
+                                        FlowCallback {
+                                            function: function.clone(),
+                                            args: split_callback_args(args.as_deref(), id),
+                                        },
+                                    );
+                                    if self.security_level_loose {
+                                        self.nodes[idx].have_callback = true;
+                                    }
                                 }
                             }
                         }
@@ -175,6 +201,15 @@ impl<'a> FlowchartSemanticContext<'a> {
                                         }
                                         "curve" => {
                                             if let Some(s) = value_to_string(v) {
+                                                if !EDGE_CURVES.contains(&s.as_str()) {
+                                                    return Err(Error::diagram_parse_fallback(
+                                                        self.diagram_type.to_string(),
+                                                        format!(
+                                                            "Invalid curve \"{s}\" for edge {target}. Valid curves are: {}. (Help: Use one of the supported curve names.)",
+                                                            EDGE_CURVES.join(", ")
+                                                        ),
+                                                    ));
+                                                }
                                                 e.interpolate = Some(s);
                                             }
                                         }
@@ -242,3 +277,37 @@ impl<'a> FlowchartSemanticContext<'a> {
         idx
     }
 }
+
+fn split_callback_args(raw: Option<&str>, id: &str) -> Vec<String> {
+    // Mirrors Mermaid FlowDB `setClickFun`: split on commas outside double quotes, strip the
+    // wrapping quotes, and fall back to the vertex id when no arguments are given.
+    let Some(raw) = raw else {
+        return vec![id.to_string()];
+    };
+
+    let mut out: Vec<String> = Vec::new();
+    let mut cur = String::new();
+    let mut in_quotes = false;
+    for ch in raw.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                cur.push(ch);
+            }
+            ',' if !in_quotes => out.push(std::mem::take(&mut cur)),
+            _ => cur.push(ch),
+        }
+    }
+    out.push(cur);
+
+    out.into_iter()
+        .map(|item| {
+            let item = item.trim();
+            if item.len() >= 2 && item.starts_with('"') && item.ends_with('"') {
+                item[1..item.len() - 1].to_string()
+            } else {
+                item.to_string()
+            }
+        })
+        .collect()
+}
@@ -169,8 +169,112 @@ const MERMAID_SHAPES_11_12_2: &[&str] = &[
     "window-pane",
 ];
 
-fn is_valid_shape_11_12_2(shape: &str) -> bool {
-    MERMAID_SHAPES_11_12_2.binary_search(&shape).is_ok()
+// Alias -> Mermaid `shortName`, mirroring the `aliases` lists in Mermaid@11.12.2
+// `rendering-elements/shapes.ts`. Public names not listed here are their own canonical form.
+const MERMAID_SHAPE_ALIASES_11_12_2: &[(&str, &str)] = &[
+    ("bow-tie-rectangle", "bow-rect"),
+    ("brace-l", "brace"),
+    ("card", "notch-rect"),
+    ("circ", "circle"),
+    ("collate", "hourglass"),
+    ("com-link", "bolt"),
+    ("comment", "brace"),
+    ("crossed-circle", "cross-circ"),
+    ("curved-trapezoid", "curv-trap"),
+    ("cylinder", "cyl"),
+    ("das", "h-cyl"),
+    ("data-store", "datastore"),
+    ("database", "cyl"),
+    ("db", "cyl"),
+    ("decision", "diam"),
+    ("diamond", "diam"),
+    ("disk", "lin-cyl"),
+    ("display", "curv-trap"),
+    ("div-proc", "div-rect"),
+    ("divided-process", "div-rect"),
+    ("divided-rectangle", "div-rect"),
+    ("document", "doc"),
+    ("documents", "docs"),
+    ("double-circle", "dbl-circ"),
+    ("event", "rounded"),
+    ("extract", "tri"),
+    ("filled-circle", "f-circ"),
+    ("flipped-triangle", "flip-tri"),
+    ("framed-circle", "fr-circ"),
+    ("framed-rectangle", "fr-rect"),
+    ("half-rounded-rectangle", "delay"),
+    ("hexagon", "hex"),
+    ("horizontal-cylinder", "h-cyl"),
+    ("in-out", "lean-r"),
+    ("internal-storage", "win-pane"),
+    ("inv-trapezoid", "trap-t"),
+    ("join", "fork"),
+    ("junction", "f-circ"),
+    ("lean-left", "lean-l"),
+    ("lean-right", "lean-r"),
+    ("lightning-bolt", "bolt"),
+    ("lin-proc", "lin-rect"),
+    ("lined-cylinder", "lin-cyl"),
+    ("lined-document", "lin-doc"),
+    ("lined-process", "lin-rect"),
+    ("lined-rectangle", "lin-rect"),
+    ("loop-limit", "notch-pent"),
+    ("manual", "trap-t"),
+    ("manual-file", "flip-tri"),
+    ("manual-input", "sl-rect"),
+    ("notched-pentagon", "notch-pent"),
+    ("notched-rectangle", "notch-rect"),
+    ("out-in", "lean-l"),
+    ("paper-tape", "flag"),
+    ("pill", "stadium"),
+    ("prepare", "hex"),
+    ("priority", "trap-b"),
+    ("proc", "rect"),
+    ("process", "rect"),
+    ("processes", "st-rect"),
+    ("procs", "st-rect"),
+    ("question", "diam"),
+    ("rectangle", "rect"),
+    ("shaded-process", "lin-rect"),
+    ("sloped-rectangle", "sl-rect"),
+    ("small-circle", "sm-circ"),
+    ("st-doc", "docs"),
+    ("stacked-document", "docs"),
+    ("stacked-rectangle", "st-rect"),
+    ("start", "sm-circ"),
+    ("stop", "fr-circ"),
+    ("stored-data", "bow-rect"),
+    ("subproc", "fr-rect"),
+    ("subprocess", "fr-rect"),
+    ("subroutine", "fr-rect"),
+    ("summary", "cross-circ"),
+    ("tag-proc", "tag-rect"),
+    ("tagged-document", "tag-doc"),
+    ("tagged-process", "tag-rect"),
+    ("tagged-rectangle", "tag-rect"),
+    ("terminal", "stadium"),
+    ("trapezoid", "trap-b"),
+    ("trapezoid-bottom", "trap-b"),
+    ("trapezoid-top", "trap-t"),
+    ("triangle", "tri"),
+    ("window-pane", "win-pane"),
+];
+
+/// Resolves a public `@{ shape: ... }` name (short name or alias) to Mermaid's `shortName`.
+///
+/// Returns `None` for unknown names and for internal camelCase/underscore shapes, which
+/// Mermaid rejects in shapeData.
+pub(super) fn canonical_shape_11_12_2(shape: &str) -> Option<&'static str> {
+    if !is_public_shape_name(shape) {
+        return None;
+    }
+    let idx = MERMAID_SHAPES_11_12_2.binary_search(&shape).ok()?;
+    let canonical = MERMAID_SHAPE_ALIASES_11_12_2
+        .binary_search_by_key(&shape, |(alias, _)| alias)
+        .map_or(MERMAID_SHAPES_11_12_2[idx], |i| {
+            MERMAID_SHAPE_ALIASES_11_12_2[i].1
+        });
+    Some(canonical)
 }
 
 pub(super) fn public_shape_names_11_12_2() -> impl Iterator<Item = &'static str> {
@@ -228,7 +332,7 @@ pub(super) fn apply_shape_data_to_node(
                         "No such shape: {shape}. Shape names should be lowercase."
                     ));
                 }
-                if !is_valid_shape_11_12_2(shape) {
+                if canonical_shape_11_12_2(shape).is_none() {
                     return Err(format!("No such shape: {shape}."));
                 }
                 node.shape = Some(shape.to_string());
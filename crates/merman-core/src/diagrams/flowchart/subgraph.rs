@@ -1,4 +1,6 @@
-use super::{FlowSubGraph, Stmt, SubgraphBlock, TitleKind, strip_wrapping_backticks, unquote};
+use super::{
+    FlowSubGraph, Stmt, SubgraphBlock, SubgraphHeader, TitleKind, strip_wrapping_backticks, unquote,
+};
 use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
@@ -17,6 +19,10 @@ struct EvalFrame<'a> {
 pub(super) struct SubgraphBuilder {
     sub_count: usize,
     pub(super) subgraphs: Vec<FlowSubGraph>,
+    explicit_ids: HashSet<String>,
+    duplicate_id: Option<String>,
+    /// Node and subgraph ids already taken; generated `subGraphN` ids skip these.
+    used_ids: HashSet<String>,
     inherit_dir: bool,
     global_dir: Option<String>,
 }
@@ -26,13 +32,27 @@ impl SubgraphBuilder {
         Self {
             sub_count: 0,
             subgraphs: Vec::new(),
+            explicit_ids: HashSet::new(),
+            duplicate_id: None,
+            used_ids: HashSet::new(),
             inherit_dir,
             global_dir,
         }
     }
 
-    pub(super) fn visit_statements(&mut self, statements: &[Stmt]) {
+    /// Builds every subgraph in `statements`.
+    ///
+    /// Mermaid's `FlowDB.addSubGraph` would push a second subgraph object with the same id; we
+    /// reject a repeated explicit id instead, since id-keyed consumers cannot tell the two apart.
+    /// For the same reason, generated `subGraphN` ids skip any node or explicit subgraph id the
+    /// diagram already uses, where upstream would reuse it.
+    pub(super) fn visit_statements(&mut self, statements: &[Stmt]) -> Result<(), String> {
+        collect_used_ids(statements, &mut self.used_ids);
         let _ = self.eval_statements(statements);
+        match self.duplicate_id.take() {
+            Some(id) => Err(format!("Duplicate subgraph id: {id}.")),
+            None => Ok(()),
+        }
     }
 
     fn eval_statements(&mut self, statements: &[Stmt]) -> Vec<StatementItem> {
@@ -126,30 +146,17 @@ impl SubgraphBuilder {
             }
         });
 
-        let raw_id = unquote(&sg.header.raw_id);
         let (title_raw, title_kind) =
             parse_subgraph_title(&sg.header.raw_title, sg.header.id_equals_title);
-        let id_raw = strip_wrapping_backticks(raw_id.trim()).0;
+        let id = explicit_subgraph_id(&sg.header);
 
-        let mut id: Option<String> = {
-            let trimmed = id_raw.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        };
-
-        // Mirror Mermaid `FlowDB.addSubGraph(...)`:
-        // `if (_id === _title && /\\s/.exec(_title.text)) id = undefined;`
-        //
-        // The important nuance is that this checks the untrimmed title token (including any
-        // extra whitespace that may have been captured into the header).
-        if sg.header.id_equals_title && sg.header.raw_title.chars().any(|c| c.is_whitespace()) {
-            id = None;
+        if let Some(id) = &id
+            && !self.explicit_ids.insert(id.clone())
+            && self.duplicate_id.is_none()
+        {
+            self.duplicate_id = Some(id.clone());
         }
-
-        let id = id.unwrap_or_else(|| format!("subGraph{}", self.sub_count));
+        let id = id.unwrap_or_else(|| self.generated_id());
         let title = title_raw.trim().to_string();
         let label_type = match title_kind {
             TitleKind::Text => "text",
@@ -175,25 +182,81 @@ impl SubgraphBuilder {
 
         id
     }
+
+    /// Mermaid's `subGraph${subCount}`, moved past any id that is already taken.
+    fn generated_id(&mut self) -> String {
+        let mut n = self.sub_count;
+        loop {
+            let id = format!("subGraph{n}");
+            if self.used_ids.insert(id.clone()) {
+                return id;
+            }
+            n += 1;
+        }
+    }
+}
+
+fn explicit_subgraph_id(header: &SubgraphHeader) -> Option<String> {
+    // Mirror Mermaid `FlowDB.addSubGraph(...)`:
+    // `if (_id === _title && /\\s/.exec(_title.text)) id = undefined;`
+    //
+    // The important nuance is that this checks the untrimmed title token (including any
+    // extra whitespace that may have been captured into the header).
+    if header.id_equals_title && header.raw_title.chars().any(|c| c.is_whitespace()) {
+        return None;
+    }
+    let raw_id = unquote(&header.raw_id);
+    let id_raw = strip_wrapping_backticks(raw_id.trim()).0;
+    let trimmed = id_raw.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+fn collect_used_ids(statements: &[Stmt], out: &mut HashSet<String>) {
+    let mut stack = vec![statements];
+    while let Some(statements) = stack.pop() {
+        for stmt in statements {
+            match stmt {
+                Stmt::Chain { nodes, .. } => out.extend(nodes.iter().map(|n| n.id.clone())),
+                Stmt::Node(n) => {
+                    out.insert(n.id.clone());
+                }
+                Stmt::ShapeData { target, .. } => {
+                    out.insert(target.clone());
+                }
+                Stmt::Subgraph(sg) => {
+                    out.extend(explicit_subgraph_id(&sg.header));
+                    stack.push(&sg.statements);
+                }
+                // `style`, `class` and `click` targets are references, not definitions: they
+                // are how a generated subgraph id gets targeted, so they must not reserve it.
+                Stmt::Style(_)
+                | Stmt::ClassAssign(_)
+                | Stmt::Click(_)
+                | Stmt::Direction(_)
+                | Stmt::ClassDef(_)
+                | Stmt::LinkStyle(_) => {}
+            }
+        }
+    }
 }
 
 fn push_statement_items(out: &mut Vec<StatementItem>, stmt: &Stmt) {
     match stmt {
-        Stmt::Chain { nodes, edges } => {
+        Stmt::Chain {
+            nodes, group_sizes, ..
+        } => {
             // Mermaid FlowDB's subgraph membership list is based on the Jison
-            // `vertexStatement.nodes` shape, which prepends the last node in a chain first
-            // (e.g. `a-->b` yields `[b, a]`).
-            //
-            // For node-only group statements (e.g. `A & B`), there are no edges and the list
-            // preserves the input order.
-            if edges.is_empty() {
-                for n in nodes {
-                    out.push(StatementItem::Id(n.id.clone()));
-                }
-            } else {
-                for n in nodes.iter().rev() {
+            // `vertexStatement.nodes` shape: each `vertexStatement link node` step prepends the
+            // new `&` group (`$node.concat($vertexStatement.nodes)`), while a group keeps its own
+            // source order. E.g. `a-->b` yields `[b, a]` and `a & b --> c & e` yields
+            // `[c, e, a, b]`. Node-only group statements (e.g. `A & B`) are a single group.
+            let mut end = nodes.len();
+            for size in group_sizes.iter().rev() {
+                let start = end.saturating_sub(*size);
+                for n in &nodes[start..end] {
                     out.push(StatementItem::Id(n.id.clone()));
                 }
+                end = start;
             }
         }
         Stmt::Node(n) => out.push(StatementItem::Id(n.id.clone())),
@@ -103,26 +103,10 @@ fn parse_acc_descr_block(cursor: &mut GanttLineCursor<'_>, first_line: &str) ->
     if !starts_with_case_insensitive(t, "accDescr") {
         return None;
     }
-    let rest = t["accDescr".len()..].trim_start();
-    let rest = rest.strip_prefix('{')?;
-
-    let mut buf = String::new();
-    if let Some(end) = rest.find('}') {
-        buf.push_str(&rest[..end]);
-        return Some(buf.trim().to_string());
-    }
-    buf.push_str(rest);
-    buf.push('\n');
-
-    while let Some((line, _line_start)) = cursor.next_line() {
-        if let Some(end) = line.find('}') {
-            buf.push_str(&line[..end]);
-            break;
-        }
-        buf.push_str(line);
-        buf.push('\n');
-    }
-    Some(buf.trim().to_string())
+    crate::common_db::collect_acc_descr_block(
+        &t["accDescr".len()..],
+        std::iter::from_fn(|| cursor.next_line().map(|(line, _line_start)| line)),
+    )
 }
 
 struct GanttLineCursor<'a> {
@@ -156,26 +156,7 @@ fn parse_acc_descr_block(lines: &mut std::str::Lines<'_>, first_line: &str) -> O
     if !starts_with_case_insensitive(t, "accDescr") {
         return None;
     }
-    let rest = t["accDescr".len()..].trim_start();
-    let rest = rest.strip_prefix('{')?;
-
-    let mut buf = String::new();
-    if let Some(end) = rest.find('}') {
-        buf.push_str(&rest[..end]);
-        return Some(buf.trim().to_string());
-    }
-    buf.push_str(rest);
-    buf.push('\n');
-
-    for line in lines {
-        if let Some(end) = line.find('}') {
-            buf.push_str(&line[..end]);
-            break;
-        }
-        buf.push_str(line);
-        buf.push('\n');
-    }
-    Some(buf.trim().to_string())
+    crate::common_db::collect_acc_descr_block(&t["accDescr".len()..], lines)
 }
 
 fn strip_comment_prefix(line: &str) -> &str {
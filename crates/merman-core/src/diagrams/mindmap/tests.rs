@@ -398,6 +398,32 @@ Unicode works too: 🤓`\"]\n      id2[\"`The dog in **the** hog... a *very long
     assert!(descr.contains("🤓"));
 }
 
+#[test]
+fn mindmap_cloud_node_with_multiline_markdown_sets_markdown_label_type() {
+    let model = parse(
+        "mindmap\n  root\n    cloud)\"`**Sky** line one\nline two`\"(\n    plain)Plain cloud(\n",
+    );
+
+    let nodes = model["nodes"].as_array().unwrap();
+    let cloud = nodes
+        .iter()
+        .find(|n| n["label"].as_str().is_some_and(|l| l.contains("Sky")))
+        .unwrap();
+    assert_eq!(cloud["shape"].as_str().unwrap(), "cloud");
+    assert_eq!(cloud["labelType"].as_str().unwrap(), "markdown");
+    assert_eq!(
+        cloud["label"].as_str().unwrap(),
+        "**Sky** line one\nline two"
+    );
+
+    let plain = nodes
+        .iter()
+        .find(|n| n["label"].as_str() == Some("Plain cloud"))
+        .unwrap();
+    assert_eq!(plain["shape"].as_str().unwrap(), "cloud");
+    assert!(plain.get("labelType").is_none());
+}
+
 #[test]
 fn mindmap_get_data_empty_when_no_nodes() {
     let model = parse("mindmap\n");
@@ -402,24 +402,8 @@ fn parse_pie_model(code: &str, meta: &ParseMetadata) -> Result<PieParseOutput> {
                 rest = "";
                 continue;
             }
-            if starts_acc_descr_block(rest) {
-                let mut parts: Vec<String> = Vec::new();
-                for next_line in raw_lines.by_ref() {
-                    let s = strip_inline_comment(next_line);
-                    if s.contains('}') {
-                        let before = s.split('}').next().unwrap_or("").trim();
-                        if !before.is_empty() {
-                            parts.push(before.to_string());
-                        }
-                        break;
-                    }
-                    let trimmed = s.trim();
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    parts.push(trimmed.to_string());
-                }
-                acc_descr = Some(parts.join("\n"));
+            if let Some(v) = parse_acc_descr_block(rest, raw_lines.by_ref()) {
+                acc_descr = Some(v);
                 rest = "";
                 continue;
             }
@@ -460,24 +444,8 @@ fn parse_pie_model(code: &str, meta: &ParseMetadata) -> Result<PieParseOutput> {
             continue;
         }
 
-        if starts_acc_descr_block(t) {
-            let mut parts: Vec<String> = Vec::new();
-            for next_line in lines.by_ref() {
-                let s = strip_inline_comment(next_line);
-                if s.contains('}') {
-                    let before = s.split('}').next().unwrap_or("").trim();
-                    if !before.is_empty() {
-                        parts.push(before.to_string());
-                    }
-                    break;
-                }
-                let trimmed = s.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                parts.push(trimmed.to_string());
-            }
-            acc_descr = Some(parts.join("\n"));
+        if let Some(v) = parse_acc_descr_block(t, lines.by_ref()) {
+            acc_descr = Some(v);
             continue;
         }
 
@@ -553,13 +521,9 @@ fn parse_acc_descr_inline(line: &str) -> Option<String> {
     None
 }
 
-fn starts_acc_descr_block(line: &str) -> bool {
-    let t = line.trim_start();
-    if !t.starts_with("accDescr") {
-        return false;
-    }
-    let rest = t.trim_start_matches("accDescr").trim_start();
-    rest.starts_with('{')
+fn parse_acc_descr_block<'a>(line: &str, lines: impl Iterator<Item = &'a str>) -> Option<String> {
+    let rest = line.trim_start().strip_prefix("accDescr")?;
+    crate::common_db::collect_acc_descr_block(rest, lines.map(strip_inline_comment))
 }
 
 fn parse_section(line: &str) -> Option<(String, f64)> {
@@ -1124,6 +1124,7 @@ mod tests {
             config: MermaidConfig::default(),
             effective_config: generated::default_site_config(),
             title: None,
+            warnings: Vec::new(),
         };
         let err = parse_quadrant_chart("quadrant-1 do\n", &meta)
             .unwrap_err()
@@ -1231,6 +1232,32 @@ mod tests {
         assert_eq!(styles["strokeWidth"].as_str().unwrap(), "10px");
     }
 
+    #[test]
+    fn styled_and_classed_points_still_validate_coordinates() {
+        let model = parse(
+            "quadrantChart\nclassDef highPriority color: #ff0000, radius: 8\nCampaign A:::highPriority: [0.3, 0.6] stroke-width: 2px\n",
+        );
+        let pts = points(&model);
+        assert_eq!(pts.len(), 1);
+        assert_eq!(pts[0]["text"].as_str().unwrap(), "Campaign A");
+        assert_eq!(pts[0]["className"].as_str().unwrap(), "highPriority");
+        assert_eq!(pts[0]["styles"]["strokeWidth"].as_str().unwrap(), "2px");
+        assert_eq!(pts[0]["x"].as_f64().unwrap(), 0.3);
+        assert_eq!(pts[0]["y"].as_f64().unwrap(), 0.6);
+
+        // Styling does not relax Mermaid's `1 | 0(.\d+)?` coordinate token.
+        for bad in [
+            "Campaign A:::highPriority: [1.3, 0.6]",
+            "Campaign A:::highPriority: [0.3, -0.6] radius: 8",
+            "Campaign A: [0.3, 2] color: #ff0000",
+        ] {
+            let err = parse_err(&format!(
+                "quadrantChart\nclassDef highPriority color: #ff0000\n{bad}\n"
+            ));
+            assert!(err.contains("invalid point coordinate"), "{bad}: {err}");
+        }
+    }
+
     #[test]
     fn parses_whole_chart_example() {
         let model = parse(
@@ -1696,6 +1696,7 @@ mod tests {
             config: MermaidConfig::default(),
             effective_config: MermaidConfig::default(),
             title: None,
+            warnings: Vec::new(),
         }
     }
 
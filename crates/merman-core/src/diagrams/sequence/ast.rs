@@ -1,3 +1,5 @@
+use crate::SourceSpan;
+
 pub(crate) enum Action {
     SetTitle(String),
     SetAccTitle(String),
@@ -83,3 +85,11 @@ pub(crate) enum Action {
         visible: bool,
     },
 }
+
+/// An action tagged with the parser-input range of the statement, or block line, that produced it.
+pub(crate) type SpannedAction = (Action, SourceSpan);
+
+pub(crate) fn spanned(actions: Vec<Action>, start: usize, end: usize) -> Vec<SpannedAction> {
+    let span = SourceSpan::new(start, end);
+    actions.into_iter().map(|action| (action, span)).collect()
+}
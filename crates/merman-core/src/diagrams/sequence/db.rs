@@ -1,4 +1,5 @@
-use crate::ParseMetadata;
+use crate::diagram::{DiagramWarningFact, SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID};
+use crate::{ParseMetadata, SourceSpan};
 use rustc_hash::FxHashMap;
 use serde_json::{Value, json};
 use std::collections::BTreeMap;
@@ -6,11 +7,15 @@ use std::collections::BTreeMap;
 use super::Action;
 use super::render_model::{
     SequenceActor, SequenceAutonumber, SequenceBox, SequenceDiagramRenderModel, SequenceMessage,
-    SequenceMessagePayload, SequenceNote,
+    SequenceMessagePayload, SequenceNote, SequenceResolvedConfig,
 };
 use super::{
-    LINETYPE_ACTIVE_END, LINETYPE_ACTIVE_START, LINETYPE_AUTONUMBER, LINETYPE_CENTRAL_CONNECTION,
-    LINETYPE_CENTRAL_CONNECTION_REVERSE, LINETYPE_NOTE,
+    LINETYPE_ACTIVE_END, LINETYPE_ACTIVE_START, LINETYPE_ALT_ELSE, LINETYPE_ALT_END,
+    LINETYPE_ALT_START, LINETYPE_AUTONUMBER, LINETYPE_BREAK_END, LINETYPE_BREAK_START,
+    LINETYPE_CENTRAL_CONNECTION, LINETYPE_CENTRAL_CONNECTION_REVERSE, LINETYPE_CRITICAL_END,
+    LINETYPE_CRITICAL_OPTION, LINETYPE_CRITICAL_START, LINETYPE_LOOP_END, LINETYPE_LOOP_START,
+    LINETYPE_NOTE, LINETYPE_OPT_END, LINETYPE_OPT_START, LINETYPE_PAR_AND, LINETYPE_PAR_END,
+    LINETYPE_PAR_OVER_START, LINETYPE_PAR_START, LINETYPE_RECT_END, LINETYPE_RECT_START,
 };
 
 #[derive(Debug, Clone)]
@@ -88,6 +93,8 @@ pub(super) struct SequenceDb {
     title: Option<String>,
     acc_title: Option<String>,
     acc_descr: Option<String>,
+
+    warning_facts: Vec<DiagramWarningFact>,
 }
 
 impl SequenceDb {
@@ -348,6 +355,21 @@ impl SequenceDb {
         });
     }
 
+    pub(super) fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub(super) fn warning_count(&self) -> usize {
+        self.warning_facts.len()
+    }
+
+    /// Points warning facts recorded since `first` at the statement that produced them.
+    pub(super) fn attach_warning_span(&mut self, first: usize, span: SourceSpan) {
+        for fact in &mut self.warning_facts[first..] {
+            fact.span.get_or_insert(span);
+        }
+    }
+
     pub(super) fn apply(&mut self, action: Action) -> std::result::Result<(), String> {
         match action {
             Action::SetTitle(t) => {
@@ -575,8 +597,7 @@ impl SequenceDb {
     }
 
     fn add_links(&mut self, actor: &str, raw_text: &str) {
-        let s = unescape_entities(raw_text);
-        let Ok(v) = serde_json::from_str::<Value>(&s) else {
+        let Some(v) = self.parse_actor_json(actor, "links", raw_text) else {
             return;
         };
         let Some(obj) = v.as_object() else {
@@ -611,8 +632,7 @@ impl SequenceDb {
     }
 
     fn add_properties(&mut self, actor: &str, raw_text: &str) {
-        let s = unescape_entities(raw_text);
-        let Ok(v) = serde_json::from_str::<Value>(&s) else {
+        let Some(v) = self.parse_actor_json(actor, "properties", raw_text) else {
             return;
         };
         let Some(obj) = v.as_object() else {
@@ -626,6 +646,23 @@ impl SequenceDb {
         }
     }
 
+    /// Mermaid's `addLinks`/`addProperties` catch `JSON.parse` failures and only log them, so a
+    /// malformed payload is recorded as a warning and the statement is otherwise ignored.
+    fn parse_actor_json(&mut self, actor: &str, kind: &str, raw_text: &str) -> Option<Value> {
+        let s = unescape_entities(raw_text);
+        match serde_json::from_str::<Value>(&s) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                let near = json_error_snippet(&s, e.line(), e.column());
+                self.warning_facts.push(DiagramWarningFact::new(
+                    SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID,
+                    format!("Invalid JSON in {kind} for actor '{actor}': {e} (near `{near}`)"),
+                ));
+                None
+            }
+        }
+    }
+
     pub(super) fn into_model(self, meta: &ParseMetadata) -> Value {
         self.into_render_model().to_compat_json(&meta.diagram_type)
     }
@@ -644,23 +681,55 @@ impl SequenceDb {
                         actor_type: a.actor_type,
                         links: a.links,
                         properties: a.properties,
+                        box_index: a.box_index,
                     },
                 );
             }
         }
 
+        let mut open_blocks = 0usize;
         let messages = std::mem::take(&mut self.messages)
             .into_iter()
-            .map(|m| SequenceMessage {
-                id: m.id,
-                from: m.from,
-                to: m.to,
-                message: m.message,
-                wrap: m.wrap,
-                message_type: m.message_type,
-                activate: m.activate,
-                placement: m.placement,
-                central_connection: m.central_connection,
+            .map(|m| {
+                let depth = match m.message_type {
+                    LINETYPE_LOOP_START
+                    | LINETYPE_ALT_START
+                    | LINETYPE_OPT_START
+                    | LINETYPE_PAR_START
+                    | LINETYPE_PAR_OVER_START
+                    | LINETYPE_RECT_START
+                    | LINETYPE_CRITICAL_START
+                    | LINETYPE_BREAK_START => {
+                        open_blocks += 1;
+                        Some(open_blocks - 1)
+                    }
+                    LINETYPE_ALT_ELSE | LINETYPE_PAR_AND | LINETYPE_CRITICAL_OPTION => {
+                        Some(open_blocks.saturating_sub(1))
+                    }
+                    LINETYPE_LOOP_END
+                    | LINETYPE_ALT_END
+                    | LINETYPE_OPT_END
+                    | LINETYPE_PAR_END
+                    | LINETYPE_RECT_END
+                    | LINETYPE_CRITICAL_END
+                    | LINETYPE_BREAK_END => {
+                        open_blocks = open_blocks.saturating_sub(1);
+                        Some(open_blocks)
+                    }
+                    _ => None,
+                };
+                SequenceMessage {
+                    id: m.id,
+                    from: m.from,
+                    to: m.to,
+                    message: m.message,
+                    wrap: m.wrap,
+                    message_type: m.message_type,
+                    activate: m.activate,
+                    placement: m.placement,
+                    central_connection: m.central_connection,
+                    depth,
+                }
             })
             .collect();
 
@@ -699,6 +768,8 @@ impl SequenceDb {
             destroyed_actors: std::mem::take(&mut self.destroyed_actors)
                 .into_iter()
                 .collect(),
+            config: SequenceResolvedConfig::default(),
+            warning_facts: std::mem::take(&mut self.warning_facts),
         }
     }
 }
@@ -963,6 +1034,28 @@ fn split_color_and_title(input: &str) -> (&str, &str) {
     (&input[..end], &input[end..])
 }
 
+/// Returns a short excerpt of `input` around the 1-based `line`/`column` reported by serde_json.
+fn json_error_snippet(input: &str, line: usize, column: usize) -> &str {
+    const CONTEXT: usize = 12;
+
+    let Some(text) = input.lines().nth(line.saturating_sub(1)) else {
+        return input.trim();
+    };
+    let mut at = column.saturating_sub(1).min(text.len());
+    while !text.is_char_boundary(at) {
+        at -= 1;
+    }
+    let mut start = at.saturating_sub(CONTEXT);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (at + CONTEXT).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    text[start..end].trim()
+}
+
 fn parse_participant_meta(input: &str) -> std::result::Result<Value, String> {
     crate::inline_config::parse_mermaid_inline_object(input)
 }
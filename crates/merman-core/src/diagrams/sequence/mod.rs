@@ -9,7 +9,13 @@ lalrpop_util::lalrpop_mod!(
 );
 
 // Mermaid 11.15.x sequence diagram constants (SequenceDB.LINETYPE / PLACEMENT).
+const LINETYPE_SOLID: i32 = 0;
+const LINETYPE_DOTTED: i32 = 1;
 const LINETYPE_NOTE: i32 = 2;
+const LINETYPE_SOLID_CROSS: i32 = 3;
+const LINETYPE_DOTTED_CROSS: i32 = 4;
+const LINETYPE_SOLID_OPEN: i32 = 5;
+const LINETYPE_DOTTED_OPEN: i32 = 6;
 const LINETYPE_LOOP_START: i32 = 10;
 const LINETYPE_LOOP_END: i32 = 11;
 const LINETYPE_ALT_START: i32 = 12;
@@ -24,6 +30,8 @@ const LINETYPE_PAR_AND: i32 = 20;
 const LINETYPE_PAR_END: i32 = 21;
 const LINETYPE_RECT_START: i32 = 22;
 const LINETYPE_RECT_END: i32 = 23;
+const LINETYPE_SOLID_POINT: i32 = 24;
+const LINETYPE_DOTTED_POINT: i32 = 25;
 const LINETYPE_AUTONUMBER: i32 = 26;
 const LINETYPE_CRITICAL_START: i32 = 27;
 const LINETYPE_CRITICAL_OPTION: i32 = 28;
@@ -31,6 +39,8 @@ const LINETYPE_CRITICAL_END: i32 = 29;
 const LINETYPE_BREAK_START: i32 = 30;
 const LINETYPE_BREAK_END: i32 = 31;
 const LINETYPE_PAR_OVER_START: i32 = 32;
+const LINETYPE_BIDIRECTIONAL_SOLID: i32 = 33;
+const LINETYPE_BIDIRECTIONAL_DOTTED: i32 = 34;
 const LINETYPE_CENTRAL_CONNECTION: i32 = 59;
 const LINETYPE_CENTRAL_CONNECTION_REVERSE: i32 = 60;
 const LINETYPE_CENTRAL_CONNECTION_DUAL: i32 = 61;
@@ -45,13 +55,15 @@ mod lexer;
 mod parse;
 mod render_model;
 
-pub(crate) use ast::Action;
+pub(crate) use ast::{Action, SpannedAction, spanned};
 pub(crate) use lexer::{LexError, Tok};
 
+pub(crate) use parse::parse_sequence_json_and_spans;
 pub use parse::{parse_sequence, parse_sequence_editor_facts, parse_sequence_model_for_render};
 pub use render_model::{
-    SequenceActor, SequenceAutonumber, SequenceBox, SequenceDiagramRenderModel, SequenceMessage,
-    SequenceMessagePayload, SequenceNote,
+    SequenceActor, SequenceArrow, SequenceAutonumber, SequenceBox, SequenceDiagramRenderModel,
+    SequenceLifelineExtent, SequenceMessage, SequenceMessagePayload, SequenceNote,
+    SequenceParBlock, SequenceResolvedConfig,
 };
 
 #[cfg(test)]
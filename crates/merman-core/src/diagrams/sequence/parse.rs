@@ -1,27 +1,48 @@
 use crate::{
-    EditorExpectedSyntax, EditorExpectedSyntaxKind, EditorSemanticFacts, EditorSemanticKind,
-    EditorSemanticSymbol, Error, ParseMetadata, Result, SourceSpan,
+    DiagramSpans, EditorExpectedSyntax, EditorExpectedSyntaxKind, EditorSemanticFacts,
+    EditorSemanticKind, EditorSemanticSymbol, Error, ParseMetadata, Result, SourceSpan,
     editor::{format_lalrpop_parse_error, lalrpop_parse_diagnostic, lalrpop_recovery_span},
 };
 use serde_json::Value;
 
-use super::SequenceDiagramRenderModel;
-use super::Tok;
 use super::db::{SequenceDb, fast_parse_sequence_signals_only_db};
 use super::lexer::Lexer;
 use super::sequence_grammar;
+use super::{Action, SpannedAction, Tok};
+use super::{SequenceDiagramRenderModel, SequenceResolvedConfig};
 
 pub fn parse_sequence(code: &str, meta: &ParseMetadata) -> Result<Value> {
     let db = parse_sequence_db(code, meta)?;
     Ok(db.into_model(meta))
 }
 
+/// Parses the semantic JSON model and the `messages` spans in parser-input coordinates.
+///
+/// Always takes the grammar path: the signals-only fast path does not track positions.
+pub(crate) fn parse_sequence_json_and_spans(
+    code: &str,
+    meta: &ParseMetadata,
+) -> Result<(Value, DiagramSpans)> {
+    let mut db = SequenceDb::new(resolve_wrap_enabled(meta));
+    let mut spans = DiagramSpans::default();
+    for (action, span) in parse_sequence_actions(code, meta)? {
+        let first = db.message_count();
+        apply_spanned(&mut db, action, span, meta)?;
+        for index in first..db.message_count() {
+            spans.messages.insert(index, span.start..span.end);
+        }
+    }
+    Ok((db.into_model(meta), spans))
+}
+
 pub fn parse_sequence_model_for_render(
     code: &str,
     meta: &ParseMetadata,
 ) -> Result<SequenceDiagramRenderModel> {
     let db = parse_sequence_db(code, meta)?;
-    Ok(db.into_render_model())
+    let mut model = db.into_render_model();
+    model.config = resolve_sequence_config(meta);
+    Ok(model)
 }
 
 pub fn parse_sequence_editor_facts(code: &str, _meta: &ParseMetadata) -> EditorSemanticFacts {
@@ -41,9 +62,8 @@ pub fn parse_sequence_editor_facts(code: &str, _meta: &ParseMetadata) -> EditorS
     facts
 }
 
-fn parse_sequence_db(code: &str, meta: &ParseMetadata) -> Result<SequenceDb> {
-    let wrap_enabled = meta
-        .effective_config
+fn resolve_wrap_enabled(meta: &ParseMetadata) -> Option<bool> {
+    meta.effective_config
         .as_value()
         .get("wrap")
         .and_then(|v| v.as_bool())
@@ -53,28 +73,60 @@ fn parse_sequence_db(code: &str, meta: &ParseMetadata) -> Result<SequenceDb> {
                 .get("sequence")
                 .and_then(|v| v.get("wrap"))
                 .and_then(|v| v.as_bool())
-        });
+        })
+}
+
+fn resolve_sequence_config(meta: &ParseMetadata) -> SequenceResolvedConfig {
+    let defaults = SequenceResolvedConfig::default();
+    let config = &meta.effective_config;
+    SequenceResolvedConfig {
+        mirror_actors: config
+            .get_bool("sequence.mirrorActors")
+            .unwrap_or(defaults.mirror_actors),
+        wrap: resolve_wrap_enabled(meta).unwrap_or(defaults.wrap),
+        show_sequence_numbers: config
+            .get_bool("sequence.showSequenceNumbers")
+            .unwrap_or(defaults.show_sequence_numbers),
+    }
+}
+
+fn parse_sequence_db(code: &str, meta: &ParseMetadata) -> Result<SequenceDb> {
+    let wrap_enabled = resolve_wrap_enabled(meta);
 
     if let Some(db) = fast_parse_sequence_signals_only_db(code, wrap_enabled) {
         return Ok(db);
     }
 
-    let actions = sequence_grammar::ActionsParser::new()
+    let mut db = SequenceDb::new(wrap_enabled);
+    for (action, span) in parse_sequence_actions(code, meta)? {
+        apply_spanned(&mut db, action, span, meta)?;
+    }
+
+    Ok(db)
+}
+
+fn apply_spanned(
+    db: &mut SequenceDb,
+    action: Action,
+    span: SourceSpan,
+    meta: &ParseMetadata,
+) -> Result<()> {
+    let first_warning = db.warning_count();
+    db.apply(action)
+        .map_err(|e| Error::diagram_parse_fallback(meta.diagram_type.clone(), e))?;
+    db.attach_warning_span(first_warning, span);
+    Ok(())
+}
+
+fn parse_sequence_actions(code: &str, meta: &ParseMetadata) -> Result<Vec<SpannedAction>> {
+    sequence_grammar::ActionsParser::new()
         .parse(Lexer::new(code))
         .map_err(|e| {
             Error::diagram_parse_diagnostic(
                 meta.diagram_type.clone(),
                 lalrpop_parse_diagnostic(&e, code.len()),
             )
-        })?;
-
-    let mut db = SequenceDb::new(wrap_enabled);
-    for a in actions {
-        db.apply(a)
-            .map_err(|e| Error::diagram_parse_fallback(meta.diagram_type.clone(), e))?;
-    }
-
-    Ok(db)
+        })
 }
 
 fn collect_sequence_editor_facts_from_tokens(code: &str) -> EditorSemanticFacts {
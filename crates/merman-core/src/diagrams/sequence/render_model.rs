@@ -1,8 +1,16 @@
 use serde::{Deserialize, Serialize, Serializer};
-use serde_json::{Map, Number, Value};
+use serde_json::{Map, Number, Value, json};
 use std::collections::BTreeMap;
 
-use super::{PLACEMENT_LEFT_OF, PLACEMENT_OVER, PLACEMENT_RIGHT_OF};
+use crate::diagram::{DiagramWarningFact, legacy_warning_messages};
+
+use super::{
+    LINETYPE_AUTONUMBER, LINETYPE_BIDIRECTIONAL_DOTTED, LINETYPE_BIDIRECTIONAL_SOLID,
+    LINETYPE_DOTTED, LINETYPE_DOTTED_CROSS, LINETYPE_DOTTED_OPEN, LINETYPE_DOTTED_POINT,
+    LINETYPE_PAR_AND, LINETYPE_PAR_END, LINETYPE_PAR_OVER_START, LINETYPE_PAR_START,
+    LINETYPE_SOLID, LINETYPE_SOLID_CROSS, LINETYPE_SOLID_OPEN, LINETYPE_SOLID_POINT,
+    PLACEMENT_LEFT_OF, PLACEMENT_OVER, PLACEMENT_RIGHT_OF,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SequenceDiagramRenderModel {
@@ -19,10 +27,46 @@ pub struct SequenceDiagramRenderModel {
     pub messages: Vec<SequenceMessage>,
     #[serde(default)]
     pub notes: Vec<SequenceNote>,
+    /// Index into `messages` of the message that creates each actor. Like Mermaid's
+    /// `records.messages.length`, control entries (block starts, `else`, `end`) count too.
     #[serde(rename = "createdActors", default)]
     pub created_actors: BTreeMap<String, usize>,
+    /// Index into `messages` of the message that destroys each actor, counted like
+    /// `created_actors`.
     #[serde(rename = "destroyedActors", default)]
     pub destroyed_actors: BTreeMap<String, usize>,
+    /// Sequence settings resolved from the effective config this model was parsed with.
+    #[serde(default)]
+    pub config: SequenceResolvedConfig,
+    #[serde(
+        default,
+        rename = "warningFacts",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub warning_facts: Vec<DiagramWarningFact>,
+}
+
+/// The `sequence.*` settings that were in effect when a diagram was parsed.
+///
+/// Defaults follow Mermaid's `defaultConfig.sequence`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SequenceResolvedConfig {
+    #[serde(rename = "mirrorActors")]
+    pub mirror_actors: bool,
+    /// Top-level `wrap` takes precedence over `sequence.wrap`.
+    pub wrap: bool,
+    #[serde(rename = "showSequenceNumbers")]
+    pub show_sequence_numbers: bool,
+}
+
+impl Default for SequenceResolvedConfig {
+    fn default() -> Self {
+        Self {
+            mirror_actors: true,
+            wrap: false,
+            show_sequence_numbers: false,
+        }
+    }
 }
 
 impl SequenceDiagramRenderModel {
@@ -32,6 +76,108 @@ impl SequenceDiagramRenderModel {
         crate::common_db::sanitize_optional_acc_descr(&mut self.acc_descr, config);
     }
 
+    /// Locates every `par` / `par over` block in [`Self::messages`], in source order.
+    ///
+    /// Nested blocks are reported separately; each `and` separator belongs to the innermost
+    /// open `par` block.
+    pub fn par_blocks(&self) -> Vec<SequenceParBlock> {
+        let mut blocks: Vec<SequenceParBlock> = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        for (idx, msg) in self.messages.iter().enumerate() {
+            match msg.message_type {
+                LINETYPE_PAR_START | LINETYPE_PAR_OVER_START => {
+                    open.push(blocks.len());
+                    blocks.push(SequenceParBlock {
+                        start: idx,
+                        separators: Vec::new(),
+                        end: None,
+                    });
+                }
+                LINETYPE_PAR_AND => {
+                    if let Some(&block) = open.last() {
+                        blocks[block].separators.push(idx);
+                    }
+                }
+                LINETYPE_PAR_END => {
+                    if let Some(block) = open.pop() {
+                        blocks[block].end = Some(idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+        blocks
+    }
+
+    /// First and last index into [`Self::messages`] touching each actor, keyed by actor id.
+    ///
+    /// A message touches an actor when it names it as `from` or `to` (signals and notes).
+    /// `createdActors` / `destroyedActors` entries override the bounds, so a renderer can start
+    /// a created actor's lifeline at its creating message and stop a destroyed one at its
+    /// destroying message. Actors no message touches are omitted.
+    pub fn lifeline_extents(&self) -> BTreeMap<String, SequenceLifelineExtent> {
+        let mut extents: BTreeMap<String, SequenceLifelineExtent> = BTreeMap::new();
+        for (idx, msg) in self.messages.iter().enumerate() {
+            for actor in [msg.from.as_deref(), msg.to.as_deref()]
+                .into_iter()
+                .flatten()
+            {
+                extents
+                    .entry(actor.to_string())
+                    .and_modify(|e| e.last_seen = idx)
+                    .or_insert(SequenceLifelineExtent {
+                        first_seen: idx,
+                        last_seen: idx,
+                    });
+            }
+        }
+        for (actor, extent) in &mut extents {
+            if let Some(&created) = self.created_actors.get(actor) {
+                extent.first_seen = created;
+            }
+            if let Some(&destroyed) = self.destroyed_actors.get(actor) {
+                extent.last_seen = destroyed;
+            }
+        }
+        extents
+    }
+
+    /// The sequence number drawn next to each entry of [`Self::messages`], or `None` where no
+    /// number is shown.
+    ///
+    /// Mirrors Mermaid's sequence renderer: numbering starts at 1 with step 1, `autonumber`
+    /// statements update start / step and toggle visibility, and `sequence.showSequenceNumbers`
+    /// (see [`Self::config`]) shows numbers on every signal regardless of `autonumber`. The
+    /// counter advances on every signal, shown or not.
+    pub fn sequence_numbers(&self) -> Vec<Option<f64>> {
+        let mut number = 1.0;
+        let mut step = 1.0;
+        let mut autonumber_visible = false;
+        self.messages
+            .iter()
+            .map(|msg| {
+                if msg.message_type == LINETYPE_AUTONUMBER {
+                    if let SequenceMessagePayload::Autonumber(autonumber) = &msg.message {
+                        // Upstream uses `start || sequenceIndex`, so `0` keeps the old value.
+                        if let Some(start) = autonumber.start.filter(|v| *v != 0.0) {
+                            number = start;
+                        }
+                        if let Some(s) = autonumber.step.filter(|v| *v != 0.0) {
+                            step = s;
+                        }
+                        autonumber_visible = autonumber.visible;
+                    }
+                    return None;
+                }
+                msg.arrow()?;
+                let shown =
+                    (autonumber_visible || self.config.show_sequence_numbers).then_some(number);
+                number = ((number + step) * 100.0).round() / 100.0;
+                shown
+            })
+            .collect()
+    }
+
     pub(crate) fn to_compat_json(&self, diagram_type: &str) -> Value {
         let mut root = serde_json::Map::with_capacity(12);
         root.insert("type".to_string(), Value::String(diagram_type.to_string()));
@@ -69,6 +215,14 @@ impl SequenceDiagramRenderModel {
         constants.insert("placement".to_string(), Value::Object(placement));
         root.insert("constants".to_string(), Value::Object(constants));
 
+        if !self.warning_facts.is_empty() {
+            root.insert("warningFacts".to_string(), json!(self.warning_facts));
+            root.insert(
+                "warnings".to_string(),
+                string_array_value(&legacy_warning_messages(&self.warning_facts)),
+            );
+        }
+
         Value::Object(root)
     }
 }
@@ -223,6 +377,40 @@ pub struct SequenceActor {
     pub links: serde_json::Map<String, Value>,
     #[serde(default)]
     pub properties: serde_json::Map<String, Value>,
+    /// Index into [`SequenceDiagramRenderModel::boxes`] of the `box` this actor belongs to.
+    #[serde(rename = "boxIndex", default, skip_serializing_if = "Option::is_none")]
+    pub box_index: Option<usize>,
+}
+
+/// Lifeline bounds located by [`SequenceDiagramRenderModel::lifeline_extents`].
+///
+/// Both fields are indices into [`SequenceDiagramRenderModel::messages`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceLifelineExtent {
+    /// The first message touching the actor (or creating it).
+    pub first_seen: usize,
+    /// The last message touching the actor (or destroying it).
+    pub last_seen: usize,
+}
+
+/// A `par` block located by [`SequenceDiagramRenderModel::par_blocks`].
+///
+/// All fields are indices into [`SequenceDiagramRenderModel::messages`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceParBlock {
+    /// The opening `par` / `par over` control message.
+    pub start: usize,
+    /// The `and` separators, in source order.
+    pub separators: Vec<usize>,
+    /// The closing `end` control message.
+    pub end: Option<usize>,
+}
+
+impl SequenceParBlock {
+    /// Number of parallel regions (lanes): one more than the number of `and` separators.
+    pub fn regions(&self) -> usize {
+        self.separators.len() + 1
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -247,12 +435,125 @@ pub struct SequenceMessage {
         skip_serializing_if = "is_zero_i32"
     )]
     pub central_connection: i32,
+    /// Block nesting level for block control messages (`loop`, `alt`/`else`, `opt`, `par`/`and`,
+    /// `rect`, `critical`/`option`, `break` and their `end`): `0` for top-level blocks, `1` for
+    /// blocks directly inside another, and so on. `None` for every other message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+impl SequenceNote {
+    /// The note text split into rows at `<br>`-style tags and literal `\n` escapes.
+    ///
+    /// `message` keeps the raw text, as Mermaid's `SequenceDB` does; this is the shared
+    /// line-break normalization applied to labels at render time.
+    pub fn lines(&self) -> Vec<String> {
+        crate::sanitize::split_mermaid_rows(&self.message)
+    }
 }
 
 impl SequenceMessage {
     pub fn message_text(&self) -> &str {
         self.message.as_text()
     }
+
+    /// [`Self::message_text`] wrapped to `width_chars` with [`crate::common::wrap_text`], or
+    /// `None` when the message does not wrap (`wrap` unset) or no width is given (`0`).
+    pub fn wrapped_lines(&self, width_chars: usize) -> Option<Vec<String>> {
+        (self.wrap && width_chars > 0)
+            .then(|| crate::common::wrap_text(self.message_text(), width_chars))
+    }
+
+    /// The arrow style of a signal message; `None` for notes and control messages.
+    pub fn arrow(&self) -> Option<SequenceArrow> {
+        SequenceArrow::from_line_type(self.message_type)
+    }
+}
+
+/// Readable names for the signal line types in Mermaid's `SequenceDB.LINETYPE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceArrow {
+    /// `->>`
+    Solid,
+    /// `-->>`
+    Dotted,
+    /// `-x`
+    SolidCross,
+    /// `--x`
+    DottedCross,
+    /// `->`
+    SolidOpen,
+    /// `-->`
+    DottedOpen,
+    /// `-)`
+    SolidPoint,
+    /// `--)`
+    DottedPoint,
+    /// `<<->>`
+    BidirectionalSolid,
+    /// `<<-->>`
+    BidirectionalDotted,
+}
+
+impl SequenceArrow {
+    pub fn from_line_type(line_type: i32) -> Option<Self> {
+        Some(match line_type {
+            LINETYPE_SOLID => Self::Solid,
+            LINETYPE_DOTTED => Self::Dotted,
+            LINETYPE_SOLID_CROSS => Self::SolidCross,
+            LINETYPE_DOTTED_CROSS => Self::DottedCross,
+            LINETYPE_SOLID_OPEN => Self::SolidOpen,
+            LINETYPE_DOTTED_OPEN => Self::DottedOpen,
+            LINETYPE_SOLID_POINT => Self::SolidPoint,
+            LINETYPE_DOTTED_POINT => Self::DottedPoint,
+            LINETYPE_BIDIRECTIONAL_SOLID => Self::BidirectionalSolid,
+            LINETYPE_BIDIRECTIONAL_DOTTED => Self::BidirectionalDotted,
+            _ => return None,
+        })
+    }
+
+    /// The numeric `type` Mermaid stores on the message.
+    pub fn line_type(self) -> i32 {
+        match self {
+            Self::Solid => LINETYPE_SOLID,
+            Self::Dotted => LINETYPE_DOTTED,
+            Self::SolidCross => LINETYPE_SOLID_CROSS,
+            Self::DottedCross => LINETYPE_DOTTED_CROSS,
+            Self::SolidOpen => LINETYPE_SOLID_OPEN,
+            Self::DottedOpen => LINETYPE_DOTTED_OPEN,
+            Self::SolidPoint => LINETYPE_SOLID_POINT,
+            Self::DottedPoint => LINETYPE_DOTTED_POINT,
+            Self::BidirectionalSolid => LINETYPE_BIDIRECTIONAL_SOLID,
+            Self::BidirectionalDotted => LINETYPE_BIDIRECTIONAL_DOTTED,
+        }
+    }
+
+    /// camelCase form of the `LINETYPE` key, e.g. `"solidCross"` for `SOLID_CROSS`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Solid => "solid",
+            Self::Dotted => "dotted",
+            Self::SolidCross => "solidCross",
+            Self::DottedCross => "dottedCross",
+            Self::SolidOpen => "solidOpen",
+            Self::DottedOpen => "dottedOpen",
+            Self::SolidPoint => "solidPoint",
+            Self::DottedPoint => "dottedPoint",
+            Self::BidirectionalSolid => "bidirectionalSolid",
+            Self::BidirectionalDotted => "bidirectionalDotted",
+        }
+    }
+
+    pub fn is_dotted(self) -> bool {
+        matches!(
+            self,
+            Self::Dotted
+                | Self::DottedCross
+                | Self::DottedOpen
+                | Self::DottedPoint
+                | Self::BidirectionalDotted
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -226,7 +226,14 @@ impl StateDb {
                     relation.description.as_deref(),
                 ),
                 Stmt::ClassDef { id, classes } => self.add_style_class(id, classes),
-                Stmt::ApplyClass { ids, class_name } => self.set_css_class(ids, class_name),
+                Stmt::ApplyClass { ids, class_name } => {
+                    let ids = ids
+                        .split(',')
+                        .map(|id| resolve_scoped_state_id(root_doc, id.trim()))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    self.set_css_class(&ids, class_name)
+                }
                 Stmt::Style { ids, styles } => self.handle_style_def(ids, styles),
                 Stmt::Direction(dir) => self.direction = Some(dir.clone()),
                 Stmt::AccTitle(t) => self.acc_title = Some(t.clone()),
@@ -755,6 +762,54 @@ fn get_dir_for_doc(doc: &[Stmt], default_dir: &str) -> String {
     dir
 }
 
+/// Resolves a scoped `Parent.child` id from a `class` statement to the nested state's id.
+///
+/// State ids are global, so the path only has to name composites that really nest the target;
+/// any other id is kept as written.
+fn resolve_scoped_state_id<'a>(root_doc: &[Stmt], id: &'a str) -> &'a str {
+    if !id.contains('.') {
+        return id;
+    }
+    let mut doc = root_doc;
+    let mut segments = id.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_none() {
+            return if doc_mentions_state(doc, segment) {
+                segment
+            } else {
+                id
+            };
+        }
+        let Some(inner) = find_composite_doc(doc, segment) else {
+            return id;
+        };
+        doc = inner;
+    }
+    id
+}
+
+fn find_composite_doc<'a>(doc: &'a [Stmt], id: &str) -> Option<&'a [Stmt]> {
+    doc.iter().find_map(|stmt| match stmt {
+        // Concurrency dividers are synthetic wrappers; look through them.
+        Stmt::State(s) if s.ty == "divider" => {
+            s.doc.as_deref().and_then(|d| find_composite_doc(d, id))
+        }
+        Stmt::State(s) if s.id == id => s.doc.as_deref(),
+        _ => None,
+    })
+}
+
+fn doc_mentions_state(doc: &[Stmt], id: &str) -> bool {
+    doc.iter().any(|stmt| match stmt {
+        Stmt::State(s) if s.ty == "divider" => {
+            s.doc.as_deref().is_some_and(|d| doc_mentions_state(d, id))
+        }
+        Stmt::State(s) => s.id == id,
+        Stmt::Relation(r) => r.state1.id == id || r.state2.id == id,
+        _ => false,
+    })
+}
+
 fn compiled_styles(css_classes: &str, classes: &IndexMap<String, StyleClass>) -> Vec<String> {
     let mut out: Vec<String> = Vec::new();
     for class_name in css_classes.split_whitespace() {
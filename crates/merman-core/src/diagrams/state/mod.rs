@@ -510,8 +510,9 @@ impl<'input> Lexer<'input> {
             let mut ids_end = self.pos;
             loop {
                 let word_start = self.pos;
+                // `.` allows scoped `Parent.child` targets for states nested in composites.
                 while let Some(b) = self.peek() {
-                    if b.is_ascii_alphanumeric() || b == b'_' {
+                    if b.is_ascii_alphanumeric() || b == b'_' || b == b'.' {
                         self.pos += 1;
                         continue;
                     }
@@ -669,6 +670,18 @@ impl<'input> Lexer<'input> {
             return Some(Err(LexError::new("Expected a state id")));
         };
         self.pop_mode();
+        let end = self.pos;
+
+        // `state Big:::someClass { ... }` applies a class to the composite itself.
+        let mut class_tok = None;
+        if self.starts_with(":::") {
+            self.pos += ":::".len();
+            let class_start = self.pos;
+            let Some(class_id) = self.read_plain_id() else {
+                return Some(Err(LexError::new("Expected a class name after ':::'")));
+            };
+            class_tok = Some((class_start, Tok::StyleClass(class_id), self.pos));
+        }
 
         // Mermaid accepts `state <id>` with a `{ ... }` block that starts on the next line:
         //
@@ -679,7 +692,6 @@ impl<'input> Lexer<'input> {
         //
         // Treat the intervening whitespace/newlines as insignificant and advance to the `{` so the
         // parser sees `CompositState` followed immediately by a `Block`.
-        let end = self.pos;
         let mut look = self.pos;
         while let Some(b) = self.input.as_bytes().get(look).copied() {
             if matches!(b, b' ' | b'\t' | b'\r') {
@@ -724,6 +736,9 @@ impl<'input> Lexer<'input> {
             }
         }
 
+        if let Some(class_tok) = class_tok {
+            self.pending.push_back(class_tok);
+        }
         Some(Ok((start, Tok::CompositState(id), end)))
     }
 
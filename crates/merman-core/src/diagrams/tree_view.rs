@@ -1071,6 +1071,7 @@ mod tests {
             config: MermaidConfig::empty_object(),
             effective_config: MermaidConfig::empty_object(),
             title: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -1435,6 +1435,7 @@ mod tests {
             config: MermaidConfig::empty_object(),
             effective_config: MermaidConfig::empty_object(),
             title: None,
+            warnings: Vec::new(),
         }
     }
 
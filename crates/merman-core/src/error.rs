@@ -162,6 +162,11 @@ pub enum Error {
 
     #[error("Invalid YAML front-matter: {message}")]
     InvalidFrontMatterYaml { message: String },
+
+    /// Input rejected by [`ParseOptions::max_input_size`](crate::ParseOptions::max_input_size)
+    /// before preprocessing.
+    #[error("Input is {size} bytes, exceeding max_input_size {limit}")]
+    InputTooLarge { size: usize, limit: usize },
 }
 
 impl Error {
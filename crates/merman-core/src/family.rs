@@ -382,6 +382,22 @@ pub(crate) fn diagram_type_supported_in_profile(
     }
 }
 
+/// Explains a detection result that differs from the full profile because a family is missing.
+pub(crate) fn profile_fallback_warning(
+    profile: BaselineRegistryProfile,
+    diagram_type: &str,
+    code: &str,
+    effective_config: &MermaidConfig,
+) -> Option<String> {
+    if profile != BaselineRegistryProfile::Tiny || diagram_type != "flowchart-v2" {
+        return None;
+    }
+    let mut scratch = effective_config.clone();
+    crate::detect::detector_flowchart_elk(code, &mut scratch).then(|| {
+        "flowchart-elk is not available in this build; parsed as flowchart-v2 instead".to_string()
+    })
+}
+
 pub(crate) fn render_model_kind_supports_diagram_type(
     model_kind: &'static str,
     diagram_type: &str,
@@ -397,6 +413,14 @@ pub fn diagram_type_family_kind(diagram_type: &str) -> Option<&'static str> {
         .find_map(|fact| (fact.id == diagram_type).then_some(fact.model_kind))
 }
 
+/// Whether `diagram_type` (a detector registry id) is parsed by the flowchart grammar.
+pub(crate) fn is_flowchart_family(diagram_type: &str) -> bool {
+    matches!(
+        diagram_type,
+        "flowchart-v2" | "flowchart" | "flowchart-elk" | "swimlane"
+    )
+}
+
 pub(crate) fn permits_json_render_fallback(
     profile: BaselineRegistryProfile,
     diagram_type: &str,
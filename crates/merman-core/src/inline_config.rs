@@ -1,6 +1,4 @@
-use serde_json::Value;
-#[cfg(any(not(feature = "full-config"), test))]
-use serde_json::{Map, Number};
+use serde_json::{Map, Number, Value};
 
 pub(crate) fn parse_mermaid_inline_object(input: &str) -> Result<Value, String> {
     #[cfg(feature = "full-config")]
@@ -14,7 +12,6 @@ pub(crate) fn parse_mermaid_inline_object(input: &str) -> Result<Value, String>
     }
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 pub(crate) fn parse_inline_config_value(input: &str) -> Result<Value, String> {
     let mut parser = Parser::new(input);
     let value = parser.parse_value()?;
@@ -77,13 +74,11 @@ fn parse_yaml_object_body(input: &str) -> Result<Value, String> {
     crate::yaml_config::parse_yaml_value(&yaml_data, crate::MAX_DIAGRAM_NESTING_DEPTH)
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 struct Parser<'a> {
     input: &'a str,
     pos: usize,
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 impl<'a> Parser<'a> {
     fn new(input: &'a str) -> Self {
         Self { input, pos: 0 }
@@ -355,7 +350,6 @@ impl<'a> Parser<'a> {
     }
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 fn parse_bare_scalar(raw: &str) -> Value {
     match raw {
         "true" => return Value::Bool(true),
@@ -377,12 +371,10 @@ fn parse_bare_scalar(raw: &str) -> Value {
     Value::String(raw.to_string())
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 fn number_is_finite(n: &Number) -> bool {
     n.as_f64().is_some_and(f64::is_finite)
 }
 
-#[cfg(any(not(feature = "full-config"), test))]
 fn leading_space_count(line: &str) -> usize {
     line.as_bytes()
         .iter()
@@ -34,11 +34,12 @@ mod yaml_config;
 pub use config::MermaidConfig;
 pub use detect::{Detector, DetectorRegistry};
 pub use diagram::{
-    BLOCK_WIDTH_WARNING_RULE_ID, DiagramRegistry, DiagramSemanticParser, DiagramWarningFact,
+    BLOCK_WIDTH_WARNING_RULE_ID, DiagramRegistry, DiagramSemanticParser, DiagramSpans,
+    DiagramWarningFact, ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID,
     FLOWCHART_EXPLICIT_DIRECTION_WARNING_RULE_ID, FLOWCHART_UNKNOWN_STYLE_TARGET_WARNING_RULE_ID,
     GIT_GRAPH_DUPLICATE_COMMIT_WARNING_RULE_ID, ParsedDiagram, ParsedDiagramRender,
     ParsedDiagramWithEditorFacts, ParsedEditorFacts, RenderDiagramRegistry, RenderSemanticModel,
-    RenderSemanticParser,
+    RenderSemanticParser, SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID,
 };
 pub use editor::{
     EditorCompletionDialect, EditorExpectedSyntax, EditorExpectedSyntaxKind, EditorRenameDomain,
@@ -120,6 +121,12 @@ fn generated_default_effective_config() -> MermaidConfig {
 pub struct ParseOptions {
     /// Return an `error` diagram model instead of an error when diagram parsing fails.
     pub suppress_errors: bool,
+    /// Reject inputs longer than this many bytes with [`Error::InputTooLarge`] before
+    /// preprocessing. `None` disables the guard.
+    pub max_input_size: Option<usize>,
+    /// Attach [`ParsedDiagram::spans`] to the `parse_diagram*` and `parse_diagram_with_type*`
+    /// results. Off by default; when off the parsers record nothing extra.
+    pub include_spans: bool,
 }
 
 impl ParseOptions {
@@ -127,6 +134,8 @@ impl ParseOptions {
     pub fn strict() -> Self {
         Self {
             suppress_errors: false,
+            max_input_size: None,
+            include_spans: false,
         }
     }
 
@@ -134,8 +143,22 @@ impl ParseOptions {
     pub fn lenient() -> Self {
         Self {
             suppress_errors: true,
+            max_input_size: None,
+            include_spans: false,
         }
     }
+
+    /// Returns these options with [`ParseOptions::max_input_size`] set to `limit`.
+    pub fn with_max_input_size(mut self, limit: usize) -> Self {
+        self.max_input_size = Some(limit);
+        self
+    }
+
+    /// Returns these options with [`ParseOptions::include_spans`] enabled.
+    pub fn with_spans(mut self) -> Self {
+        self.include_spans = true;
+        self
+    }
 }
 
 /// Metadata extracted before semantic diagram parsing.
@@ -150,12 +173,19 @@ pub struct ParseMetadata {
     pub effective_config: MermaidConfig,
     /// Sanitized Mermaid title from front-matter/directives, when present.
     pub title: Option<String>,
+    /// Non-fatal notes from preprocessing and detection, such as ignored directive types or the
+    /// tiny-profile `flowchart-elk` fallback reported by
+    /// [`Engine::with_profile_fallback_warnings`]. Empty for most inputs.
+    pub warnings: Vec<String>,
 }
 
 /// Headless Mermaid parser engine.
 ///
 /// An engine owns detector/parser registries and a site-level Mermaid configuration. It is cheap
 /// to clone when callers need per-request option variants.
+///
+/// `Engine` is `Send + Sync` and parsing never mutates it, so one engine can be shared (for
+/// example behind an `Arc`) and its `&self` parse methods called concurrently from many threads.
 #[derive(Debug, Clone)]
 pub struct Engine {
     registry: DetectorRegistry,
@@ -165,6 +195,7 @@ pub struct Engine {
     default_effective_config: MermaidConfig,
     fixed_today_local: Option<chrono::NaiveDate>,
     fixed_local_offset_minutes: Option<i32>,
+    profile_fallback_warnings: bool,
 }
 
 impl Default for Engine {
@@ -180,6 +211,7 @@ impl Default for Engine {
             default_effective_config,
             fixed_today_local: None,
             fixed_local_offset_minutes: None,
+            profile_fallback_warnings: false,
         }
     }
 }
@@ -234,6 +266,16 @@ impl Engine {
         self
     }
 
+    /// Reports registry-profile fallbacks in [`ParseMetadata::warnings`].
+    ///
+    /// The tiny profile has no `flowchart-elk` detector, so ELK flowcharts are detected as
+    /// `flowchart-v2` and laid out with dagre. That fallback stays in place; enabling this only
+    /// makes it visible. Full builds never fall back and never warn.
+    pub fn with_profile_fallback_warnings(mut self, enabled: bool) -> Self {
+        self.profile_fallback_warnings = enabled;
+        self
+    }
+
     /// Returns the fixed local timezone offset configured for this engine.
     pub fn fixed_local_offset_minutes(&self) -> Option<i32> {
         self.fixed_local_offset_minutes
@@ -370,6 +412,8 @@ impl Engine {
 
     /// Synchronous variant of [`Engine::parse_diagram`].
     ///
+    /// Safe to call concurrently on a shared `&Engine`; each call keeps its own parse state.
+    ///
     /// Note: callers that want “always returns a diagram” behavior can set
     /// [`ParseOptions::suppress_errors`] to `true` to get an `error` diagram on parse failures.
     pub fn parse_diagram_sync(
@@ -2,6 +2,16 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
+pub(crate) const LINE_SOLID: i32 = 0;
+pub(crate) const LINE_DOTTED: i32 = 1;
+
+pub(crate) const REL_AGGREGATION: i32 = 0;
+pub(crate) const REL_EXTENSION: i32 = 1;
+pub(crate) const REL_COMPOSITION: i32 = 2;
+pub(crate) const REL_DEPENDENCY: i32 = 3;
+pub(crate) const REL_LOLLIPOP: i32 = 4;
+pub(crate) const REL_NONE: i32 = -1;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassDiagram {
     #[serde(rename = "type")]
@@ -91,6 +101,48 @@ pub struct ClassMember {
     pub css_style: String,
 }
 
+impl ClassMember {
+    /// Default value of an attribute written as `name: Type = value`, e.g. `0` for
+    /// `+count: int = 0`. Mermaid keeps the whole text in `id`/`displayText` (with `=` escaped
+    /// as `&#61;` unless HTML labels are enabled); methods never carry a default.
+    pub fn default_value(&self) -> Option<&str> {
+        if self.member_type != "attribute" {
+            return None;
+        }
+        let (_, value) = self
+            .id
+            .split_once("&#61;")
+            .or_else(|| self.id.split_once('='))?;
+        let value = value.trim();
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Modifiers on the member: `static` / `abstract` for the `$` / `*` classifiers, followed by
+    /// any `{name}` annotations (e.g. `{abstract}`) written in the member text.
+    pub fn annotations(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        match self.classifier.as_str() {
+            "$" => out.push("static"),
+            "*" => out.push("abstract"),
+            _ => {}
+        }
+        for text in [&self.id, &self.return_type] {
+            let mut rest = text.as_str();
+            while let Some(open) = rest.find('{') {
+                let Some(len) = rest[open + 1..].find('}') else {
+                    break;
+                };
+                let name = rest[open + 1..open + 1 + len].trim();
+                if !name.is_empty() && !out.contains(&name) {
+                    out.push(name);
+                }
+                rest = &rest[open + len + 2..];
+            }
+        }
+        out
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassRelation {
     pub id: String,
@@ -112,6 +164,38 @@ pub struct RelationShape {
     pub line_type: i32,
 }
 
+impl RelationShape {
+    /// Readable form of `line_type`: `"dashed"` for `..` links, `"solid"` otherwise.
+    pub fn line(&self) -> &'static str {
+        if self.line_type == LINE_DOTTED {
+            "dashed"
+        } else {
+            "solid"
+        }
+    }
+
+    /// Marker name drawn at the `id1` end (`type1`).
+    pub fn arrow_start(&self) -> &'static str {
+        relation_marker_name(self.type1)
+    }
+
+    /// Marker name drawn at the `id2` end (`type2`).
+    pub fn arrow_end(&self) -> &'static str {
+        relation_marker_name(self.type2)
+    }
+}
+
+fn relation_marker_name(ty: i32) -> &'static str {
+    match ty {
+        REL_EXTENSION => "triangle",
+        REL_DEPENDENCY => "arrow",
+        REL_AGGREGATION => "aggregation",
+        REL_COMPOSITION => "composition",
+        REL_LOLLIPOP => "lollipop",
+        _ => "none",
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClassNote {
     pub id: String,
@@ -4,8 +4,8 @@ use crate::{
     preprocess_diagram, preprocess_diagram_with_known_type, runtime, sanitize, theme,
 };
 use diagram::{
-    DiagramWarningFact, ParsedDiagram, ParsedDiagramRender, ParsedDiagramWithEditorFacts,
-    ParsedEditorFacts, RenderSemanticModel,
+    DiagramSpans, DiagramWarningFact, ParsedDiagram, ParsedDiagramRender,
+    ParsedDiagramWithEditorFacts, ParsedEditorFacts, RenderSemanticModel,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -124,6 +124,24 @@ impl<'a> EditorParseSourceMap<'a> {
         }
     }
 
+    /// Moves parser-input spans to original-source ranges, dropping any that cannot be placed.
+    /// Uses the same unique-fragment fallback as warning facts, so spans survive comment and
+    /// directive stripping.
+    fn remap_diagram_spans(&self, spans: &mut DiagramSpans) {
+        let remap = |range: &mut std::ops::Range<usize>| {
+            let Some(span) =
+                self.try_remap_warning_source_span(SourceSpan::new(range.start, range.end))
+            else {
+                return false;
+            };
+            *range = span.start..span.end;
+            true
+        };
+        spans.nodes.retain(|_, range| remap(range));
+        spans.edges.retain(|_, range| remap(range));
+        spans.messages.retain(|_, range| remap(range));
+    }
+
     fn remap_source_span(&self, span: SourceSpan) -> SourceSpan {
         SourceSpan::new(self.remap_offset(span.start), self.remap_offset(span.end))
     }
@@ -383,24 +401,59 @@ impl<'a> ParsePipeline<'a> {
     }
 
     pub(crate) fn parse_json(&self, timing: ParseTiming) -> Result<Option<ParsedDiagram>> {
+        let include_spans = self.options.include_spans;
         self.parse_model(
             timing,
             |pipeline, code, meta| {
+                if include_spans {
+                    return pipeline
+                        .parse_json_and_spans(code, meta)
+                        .map(|(model, spans)| (model, Some(spans)));
+                }
                 diagram::parse_or_unsupported(
                     &pipeline.engine.diagram_registry,
                     &meta.diagram_type,
                     code,
                     meta,
                 )
+                .map(|model| (model, None))
             },
-            common_db::apply_common_db_sanitization,
+            |(model, _), config| common_db::apply_common_db_sanitization(model, config),
             error_diagram::suppressed_error_diagram,
-            |meta, model| ParsedDiagram { meta, model },
-            Self::remap_value_warning_facts,
+            |meta, (model, spans)| ParsedDiagram { meta, model, spans },
+            |(model, spans), source_map| {
+                Self::remap_value_warning_facts(model, source_map);
+                if let Some(spans) = spans {
+                    source_map.remap_diagram_spans(spans);
+                }
+            },
             |_| None,
         )
     }
 
+    /// Span-recording counterpart of [`diagram::parse_or_unsupported`]. Families without span
+    /// support parse through the registry as usual and report empty spans.
+    fn parse_json_and_spans(
+        &self,
+        code: &str,
+        meta: &ParseMetadata,
+    ) -> Result<(serde_json::Value, DiagramSpans)> {
+        let registry = &self.engine.diagram_registry;
+        match meta.diagram_type.as_str() {
+            diagram_type
+                if family::is_flowchart_family(diagram_type)
+                    && registry.get(diagram_type).is_some() =>
+            {
+                crate::diagrams::flowchart::parse_flowchart_json_and_spans(code, meta)
+            }
+            "sequence" if registry.get("sequence").is_some() => {
+                crate::diagrams::sequence::parse_sequence_json_and_spans(code, meta)
+            }
+            diagram_type => diagram::parse_or_unsupported(registry, diagram_type, code, meta)
+                .map(|model| (model, DiagramSpans::default())),
+        }
+    }
+
     pub(crate) fn parse_json_with_editor_facts(
         &self,
         timing: ParseTiming,
@@ -418,7 +471,7 @@ impl<'a> ParsePipeline<'a> {
 
         let parse_start = runtime::timing_start(timing_enabled);
         let parsed = match meta.diagram_type.as_str() {
-            "flowchart-v2" | "flowchart" | "flowchart-elk" | "swimlane" => {
+            diagram_type if family::is_flowchart_family(diagram_type) => {
                 let parse_res = self.with_fixed_time(|| {
                     crate::diagrams::flowchart::parse_flowchart_json_and_editor_facts(
                         editor_input,
@@ -461,7 +514,11 @@ impl<'a> ParsePipeline<'a> {
                 let facts =
                     self.finish_editor_semantic_facts(facts, &source_map, directive_prefixes);
                 return Ok(Some(ParsedDiagramWithEditorFacts {
-                    diagram: ParsedDiagram { meta, model },
+                    diagram: ParsedDiagram {
+                        meta,
+                        model,
+                        spans: None,
+                    },
                     editor_facts: ParsedEditorFacts::Available(facts),
                 }));
             }
@@ -506,7 +563,11 @@ impl<'a> ParsePipeline<'a> {
                     sanitize,
                     input_bytes: self.text.len(),
                 });
-                ParsedDiagram { meta, model }
+                ParsedDiagram {
+                    meta,
+                    model,
+                    spans: None,
+                }
             }
         };
 
@@ -575,7 +636,7 @@ impl<'a> ParsePipeline<'a> {
         }
 
         let facts = match meta.diagram_type.as_str() {
-            "flowchart-v2" | "flowchart" | "flowchart-elk" | "swimlane" => {
+            diagram_type if family::is_flowchart_family(diagram_type) => {
                 crate::diagrams::flowchart::parse_flowchart_editor_facts(editor_input, &meta)?
             }
             "sequence" => {
@@ -808,6 +869,15 @@ impl<'a> ParsePipeline<'a> {
     }
 
     fn preprocess(&self) -> Result<Option<(String, ParseMetadata)>> {
+        if let Some(limit) = self.options.max_input_size
+            && self.text.len() > limit
+        {
+            return Err(Error::InputTooLarge {
+                size: self.text.len(),
+                limit,
+            });
+        }
+
         match self.source {
             ParseSource::Detect => self.preprocess_and_detect(),
             ParseSource::KnownType(diagram_type) => self.preprocess_and_assume_type(diagram_type),
@@ -837,6 +907,15 @@ impl<'a> ParsePipeline<'a> {
                 return Err(err);
             }
         };
+        let mut warnings = pre.warnings;
+        if self.engine.profile_fallback_warnings {
+            warnings.extend(family::profile_fallback_warning(
+                self.engine.registry.profile(),
+                &diagram_type,
+                &pre.code,
+                &effective_config,
+            ));
+        }
         family::apply_diagram_type_config_defaults(
             &diagram_type,
             &pre.config,
@@ -862,6 +941,7 @@ impl<'a> ParsePipeline<'a> {
                 config: pre.config,
                 effective_config,
                 title,
+                warnings,
             },
         )))
     }
@@ -908,6 +988,7 @@ impl<'a> ParsePipeline<'a> {
                 config: pre.config,
                 effective_config,
                 title,
+                warnings: pre.warnings,
             },
         )))
     }
@@ -919,14 +1000,7 @@ impl<'a> ParsePipeline<'a> {
     }
 
     fn effective_config_before_detect(&self, overrides: &MermaidConfig) -> MermaidConfig {
-        if overrides.is_empty_object() {
-            return self.engine.site_config.clone();
-        }
-
-        let mut effective_config = self.engine.site_config.clone();
-        let effective_overrides = effective_config.secure_filtered_overrides(overrides);
-        effective_config.deep_merge(effective_overrides.as_value());
-        effective_config
+        self.engine.site_config.with_secure_overrides(overrides)
     }
 }
 
@@ -7,6 +7,8 @@ pub struct PreprocessResult {
     pub code: String,
     pub title: Option<String>,
     pub config: MermaidConfig,
+    /// Non-fatal notes collected while preprocessing, such as ignored directive types.
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -78,10 +80,12 @@ pub fn preprocess_diagram_with_known_type(
     registry: &DetectorRegistry,
     diagram_type: Option<&str>,
 ) -> Result<PreprocessResult> {
-    let cleaned = cleanup_text(input);
+    let cleaned = cleanup_text(input, |text| {
+        is_flowchart_family_source(text, registry, diagram_type)
+    });
     let (without_frontmatter, title, mut frontmatter_config) =
         process_frontmatter(cleaned.as_ref())?;
-    let (without_directives, directive_config) =
+    let (without_directives, directive_config, warnings) =
         process_directives(without_frontmatter, registry, diagram_type)?;
 
     frontmatter_config.deep_merge(directive_config.as_value());
@@ -91,10 +95,11 @@ pub fn preprocess_diagram_with_known_type(
         code: code.into_owned(),
         title,
         config: frontmatter_config,
+        warnings,
     })
 }
 
-fn cleanup_text(input: &str) -> Cow<'_, str> {
+fn cleanup_text(input: &str, is_flowchart: impl FnOnce(&str) -> bool) -> Cow<'_, str> {
     let mut s: Cow<'_, str> = if input.contains('\r') {
         Cow::Owned(normalize_crlf(input))
     } else {
@@ -107,7 +112,13 @@ fn cleanup_text(input: &str) -> Cow<'_, str> {
     //
     // Source of truth: `packages/mermaid/src/utils.ts::encodeEntities` at Mermaid@11.12.2.
     if s.contains('#') {
-        s = Cow::Owned(encode_mermaid_entities_like_upstream(s.as_ref()));
+        // Only `;`-separated hex styles care about the diagram family, so skip detection
+        // otherwise.
+        let split_statements = s.contains(';') && is_flowchart(s.as_ref());
+        s = Cow::Owned(encode_mermaid_entities_like_upstream(
+            s.as_ref(),
+            split_statements,
+        ));
     }
 
     // Mermaid performs this HTML attribute rewrite as part of preprocessing.
@@ -202,7 +213,7 @@ fn normalize_html_attributes_like_upstream(attributes: &str) -> String {
     out
 }
 
-fn encode_mermaid_entities_like_upstream(text: &str) -> String {
+fn encode_mermaid_entities_like_upstream(text: &str, split_statements: bool) -> String {
     if !text.contains('#') {
         return text.to_string();
     }
@@ -212,14 +223,17 @@ fn encode_mermaid_entities_like_upstream(text: &str) -> String {
     // 1) Protect `style...:#...;` and `classDef...:#...;` so color hex fragments are not mistaken
     //    as entities by the `/#\\w+;/g` pass.
     // 2) Encode `#<name>;` and `#<number>;` sequences into placeholders that do not contain `#`/`;`.
+    //
+    // Flowcharts additionally keep `;`-separated style statements apart (`split_statements`);
+    // see `strip_hex_style_semicolons_from_line`.
     let mut txt = text.to_string();
 
     if txt.contains("style") && txt.contains(';') {
-        txt = strip_hex_style_semicolons_like_upstream(&txt, "style");
+        txt = strip_hex_style_semicolons_like_upstream(&txt, "style", split_statements);
     }
 
     if txt.contains("classDef") && txt.contains(';') {
-        txt = strip_hex_style_semicolons_like_upstream(&txt, "classDef");
+        txt = strip_hex_style_semicolons_like_upstream(&txt, "classDef", split_statements);
     }
 
     if txt.contains(';') {
@@ -266,37 +280,104 @@ fn is_mermaid_js_word_byte(byte: u8) -> bool {
     byte.is_ascii_alphanumeric() || byte == b'_'
 }
 
-fn strip_hex_style_semicolons_like_upstream(text: &str, keyword: &str) -> String {
+/// Whether `input` belongs to the flowchart family, using the caller's known type or else the
+/// detector registry (which handles front-matter, directives and comments itself).
+fn is_flowchart_family_source(
+    input: &str,
+    registry: &DetectorRegistry,
+    diagram_type: Option<&str>,
+) -> bool {
+    match diagram_type {
+        Some(diagram_type) => crate::family::is_flowchart_family(diagram_type),
+        None => registry
+            .detect_type(input, &mut MermaidConfig::empty_object())
+            .is_ok_and(crate::family::is_flowchart_family),
+    }
+}
+
+fn strip_hex_style_semicolons_like_upstream(
+    text: &str,
+    keyword: &str,
+    split_statements: bool,
+) -> String {
     let mut out = String::with_capacity(text.len());
     let mut line_start = 0usize;
 
     for (idx, ch) in text.char_indices() {
         if ch == '\n' {
-            strip_hex_style_semicolons_from_line(&text[line_start..idx], keyword, &mut out);
+            strip_hex_style_semicolons_from_line(
+                &text[line_start..idx],
+                keyword,
+                split_statements,
+                &mut out,
+            );
             out.push('\n');
             line_start = idx + ch.len_utf8();
         }
     }
 
-    strip_hex_style_semicolons_from_line(&text[line_start..], keyword, &mut out);
+    strip_hex_style_semicolons_from_line(&text[line_start..], keyword, split_statements, &mut out);
     out
 }
 
-fn strip_hex_style_semicolons_from_line(line: &str, keyword: &str, out: &mut String) {
+fn strip_hex_style_semicolons_from_line(
+    line: &str,
+    keyword: &str,
+    split_statements: bool,
+    out: &mut String,
+) {
     let mut cursor = 0usize;
-    while let Some(semicolon) = find_hex_style_match(line, keyword, cursor) {
-        out.push_str(&line[cursor..semicolon]);
+    while let Some((start, semicolon)) = find_hex_style_match(line, keyword, cursor) {
+        out.push_str(&line[cursor..start]);
+        if split_statements {
+            // Upstream's greedy `/style.*:\S*#.*;/` only drops the last `;` on the line, so with
+            // `;`-separated statements (`style a fill:#fff; style b fill:#000;`) the earlier
+            // `#fff;` is later encoded as an entity and the statements are merged. Turn those
+            // separators into newlines instead (same byte length, so spans stay valid).
+            push_with_hex_color_separators_as_newlines(&line[start..semicolon], out);
+            if !line[semicolon + 1..].trim().is_empty() {
+                out.push('\n');
+            }
+        } else {
+            out.push_str(&line[start..semicolon]);
+        }
         cursor = semicolon + 1;
     }
     out.push_str(&line[cursor..]);
 }
 
-fn find_hex_style_match(line: &str, keyword: &str, search_start: usize) -> Option<usize> {
+fn push_with_hex_color_separators_as_newlines(segment: &str, out: &mut String) {
+    let mut cursor = 0usize;
+    for (idx, _) in segment.match_indices(';') {
+        if ends_with_hex_color_value(&segment[..idx]) {
+            out.push_str(&segment[cursor..idx]);
+            out.push('\n');
+            cursor = idx + 1;
+        }
+    }
+    out.push_str(&segment[cursor..]);
+}
+
+fn ends_with_hex_color_value(prefix: &str) -> bool {
+    let digits = prefix
+        .bytes()
+        .rev()
+        .take_while(u8::is_ascii_hexdigit)
+        .count();
+    let Some(before_digits) = prefix.len().checked_sub(digits + 1) else {
+        return false;
+    };
+    (3..=8).contains(&digits)
+        && prefix.as_bytes()[before_digits] == b'#'
+        && prefix[..before_digits].trim_end().ends_with(':')
+}
+
+fn find_hex_style_match(line: &str, keyword: &str, search_start: usize) -> Option<(usize, usize)> {
     let mut probe = search_start;
     while let Some(rel_start) = line[probe..].find(keyword) {
         let start = probe + rel_start;
         if let Some(semicolon) = find_hex_style_match_end(line, start + keyword.len()) {
-            return Some(semicolon);
+            return Some((start, semicolon));
         }
         probe = start + keyword.len();
     }
@@ -543,10 +624,14 @@ fn process_directives<'a>(
     input: &'a str,
     registry: &DetectorRegistry,
     diagram_type: Option<&str>,
-) -> Result<(Cow<'a, str>, MermaidConfig)> {
+) -> Result<(Cow<'a, str>, MermaidConfig, Vec<String>)> {
     let directives = detect_directives(input)?;
     if directives.is_empty() {
-        return Ok((Cow::Borrowed(input), MermaidConfig::empty_object()));
+        return Ok((
+            Cow::Borrowed(input),
+            MermaidConfig::empty_object(),
+            Vec::new(),
+        ));
     }
     let init = detect_init(&directives, input, registry, diagram_type)?;
     let wrap = directives.iter().any(|d| d.ty == "wrap");
@@ -556,7 +641,14 @@ fn process_directives<'a>(
         merged.set_value("wrap", Value::Bool(true));
     }
 
-    Ok((Cow::Owned(remove_directives(input)), merged))
+    // Mermaid only acts on `init`/`initialize` and `wrap`; anything else is stripped silently.
+    let warnings = directives
+        .iter()
+        .filter(|d| !matches!(d.ty.as_str(), "init" | "initialize" | "wrap"))
+        .map(|d| format!("ignored unsupported directive `{}`", d.ty))
+        .collect();
+
+    Ok((Cow::Owned(remove_directives(input)), merged, warnings))
 }
 
 fn detect_init(
@@ -926,8 +1018,15 @@ fn parse_directive(raw: &str) -> Result<Option<Directive>> {
 fn parse_directive_config_value(input: &str) -> Result<Value> {
     #[cfg(feature = "full-config")]
     {
-        json5::from_str::<Value>(input).map_err(|e| Error::InvalidDirectiveJson {
-            message: e.to_string(),
+        // JSON5 covers quoted and unquoted keys and both quote styles. Bare values such as
+        // `{theme: base}` are not JSON5, so retry with the tolerant inline parser before
+        // reporting the JSON5 error.
+        json5::from_str::<Value>(input).or_else(|e| {
+            crate::inline_config::parse_inline_config_value(input).map_err(|_| {
+                Error::InvalidDirectiveJson {
+                    message: e.to_string(),
+                }
+            })
         })
     }
 
@@ -1060,35 +1159,72 @@ mod tests {
     #[test]
     fn encode_entity_placeholders_matches_mermaid_ascii_word_shape() {
         assert_eq!(
-            encode_mermaid_entities_like_upstream("Hello #there; #andHere;#77653;"),
+            encode_mermaid_entities_like_upstream("Hello #there; #andHere;#77653;", false),
             "Hello ﬂ°there¶ß ﬂ°andHere¶ßﬂ°°77653¶ß"
         );
         assert_eq!(
             encode_mermaid_entities_like_upstream(
-                "style this; is ; everything :something#not-nothing; and this too;"
+                "style this; is ; everything :something#not-nothing; and this too;",
+                false
             ),
             "style this; is ; everything :something#not-nothing; and this too"
         );
         assert_eq!(
             encode_mermaid_entities_like_upstream(
-                "classDef this; is ; everything :something#not-nothing; and this too;"
+                "classDef this; is ; everything :something#not-nothing; and this too;",
+                false
             ),
             "classDef this; is ; everything :something#not-nothing; and this too"
         );
         assert_eq!(
-            encode_mermaid_entities_like_upstream("style a fill:#fff; style b fill:#000;"),
+            encode_mermaid_entities_like_upstream("style a fill:#fff; style b fill:#000;", false),
             "style a fill:ﬂ°fff¶ß style b fill:#000"
         );
         assert_eq!(
-            encode_mermaid_entities_like_upstream("style a fill: #fff;"),
+            encode_mermaid_entities_like_upstream("style a fill: #fff;", false),
             "style a fill: ﬂ°fff¶ß"
         );
         assert_eq!(
-            encode_mermaid_entities_like_upstream("#é; #+123; #has-dash;"),
+            encode_mermaid_entities_like_upstream("#é; #+123; #has-dash;", false),
             "#é; #+123; #has-dash;"
         );
     }
 
+    #[test]
+    fn flowchart_semicolon_separated_hex_styles_are_split_instead_of_encoded() {
+        let registry = DetectorRegistry::pinned_mermaid_baseline_full();
+        let cleanup = |text: &str, diagram_type: Option<&str>| {
+            cleanup_text(text, |text| {
+                is_flowchart_family_source(text, &registry, diagram_type)
+            })
+            .into_owned()
+        };
+
+        assert_eq!(
+            cleanup("graph TD;style a fill:#fff; style b fill:#000;", None),
+            "graph TD;style a fill:#fff\n style b fill:#000"
+        );
+        assert_eq!(
+            cleanup(
+                "---\ntitle: x\n---\n%% note\nflowchart LR\nclassDef c fill:#fff;A:::c",
+                None
+            ),
+            "---\ntitle: x\n---\n%% note\nflowchart LR\nclassDef c fill:#fff\nA:::c"
+        );
+        assert_eq!(
+            cleanup("swimlane-beta LR\nclassDef c fill:#fff;A:::c", None),
+            "swimlane-beta LR\nclassDef c fill:#fff\nA:::c"
+        );
+        assert_eq!(
+            cleanup("A-->B\nstyle A fill:#fff;B", Some("flowchart-v2")),
+            "A-->B\nstyle A fill:#fff\nB"
+        );
+        assert_eq!(
+            cleanup("block\nA; classDef X fill:#000; class A X", None),
+            "block\nA; classDef X fill:#000 class A X"
+        );
+    }
+
     #[test]
     fn sanitize_directive_handles_deep_values_with_small_stack() {
         const DEPTH: usize = 2_048;
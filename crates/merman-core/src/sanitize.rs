@@ -30,6 +30,16 @@ fn break_to_placeholder(input: &str) -> String {
     out
 }
 
+/// Splits label text into rows like Mermaid's `common.getRows`: `<br>`, `<br/>` (any case and
+/// inner whitespace) and literal `\n` escapes all start a new row.
+pub(crate) fn split_mermaid_rows(input: &str) -> Vec<String> {
+    break_to_placeholder(input)
+        .replace("\\n", "#br#")
+        .split("#br#")
+        .map(str::to_string)
+        .collect()
+}
+
 fn placeholder_to_break(input: &str) -> String {
     input.replace("#br#", "<br/>")
 }
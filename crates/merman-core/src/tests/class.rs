@@ -346,6 +346,28 @@ classDef pink fill:#f9f
     assert_eq!(c["styles"], json!(["fill:#f9f"]));
 }
 
+#[test]
+fn parse_diagram_class_classdef_and_style_keep_inner_spacing_like_mermaid() {
+    let engine = Engine::new();
+    let text = r#"classDiagram
+class A
+classDef foo background: #bbb , border:1px solid red
+style A fill: #f9f ,stroke: #333
+"#;
+
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["styleClasses"]["foo"]["styles"],
+        json!(["background: #bbb", "border:1px solid red"])
+    );
+    assert_eq!(
+        res.model["classes"]["A"]["styles"],
+        json!(["fill: #f9f", "stroke: #333"])
+    );
+}
+
 #[test]
 fn parse_diagram_class_multiple_classdefs_merge_styles() {
     let engine = Engine::new();
@@ -801,3 +823,19 @@ fn parse_class_editor_facts_continue_after_advancing_lexer_error() {
     assert_eq!(facts.completeness, EditorSemanticCompleteness::Recovered);
     assert!(facts.symbols.iter().any(|symbol| symbol.name == "User"));
 }
+
+#[test]
+fn parse_diagram_class_class_def_splits_color_into_text_styles() {
+    let engine = Engine::new();
+    let text = "classDiagram\nclass A\nclassDef warn fill:#f00,color:pink,font-weight:bold\ncssClass \"A\" warn\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let warn = &res.model["styleClasses"]["warn"];
+    assert_eq!(
+        warn["styles"],
+        json!(["fill:#f00", "color:pink", "font-weight:bold"])
+    );
+    assert_eq!(warn["textStyles"], json!(["color:pink"]));
+}
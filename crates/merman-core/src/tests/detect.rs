@@ -45,6 +45,37 @@ fn tiny_build_flowchart_elk_falls_back_to_flowchart_v2() {
         .unwrap();
     assert_eq!(res.diagram_type, "flowchart-v2");
     assert_eq!(res.effective_config.get_str("layout"), Some("dagre"));
+    assert!(res.warnings.is_empty());
+}
+
+#[cfg(not(feature = "full-registry"))]
+#[test]
+fn tiny_build_flowchart_elk_fallback_warns_when_enabled() {
+    let engine = Engine::new().with_profile_fallback_warnings(true);
+    let res = block_on(engine.parse_metadata("flowchart-elk TD\nA-->B", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.diagram_type, "flowchart-v2");
+    assert_eq!(
+        res.warnings,
+        ["flowchart-elk is not available in this build; parsed as flowchart-v2 instead"]
+    );
+
+    let res = block_on(engine.parse_metadata("flowchart TD\nA-->B", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert!(res.warnings.is_empty());
+}
+
+#[cfg(feature = "full-registry")]
+#[test]
+fn full_build_flowchart_elk_never_reports_a_profile_fallback() {
+    let engine = Engine::new().with_profile_fallback_warnings(true);
+    let res = block_on(engine.parse_metadata("flowchart-elk TD\nA-->B", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.diagram_type, "flowchart-elk");
+    assert!(res.warnings.is_empty());
 }
 
 #[test]
@@ -380,3 +411,25 @@ fn auto_detect_common_headers_with_deep_config_small_stack() {
         .join()
         .expect("common header detection should finish without stack overflow");
 }
+
+#[test]
+fn detects_space_and_tab_indented_headers() {
+    let engine = Engine::new();
+    for (text, expected) in [
+        ("   sequenceDiagram\n   Alice->>Bob: Hi\n", "sequence"),
+        ("\tsequenceDiagram\n\tAlice->>Bob: Hi\n", "sequence"),
+        ("    flowchart TD\n    A-->B\n", "flowchart-v2"),
+        ("\t\tflowchart TD\n\t\tA-->B\n", "flowchart-v2"),
+        ("\n  \n \tflowchart TD\n A-->B\n", "flowchart-v2"),
+    ] {
+        let meta = block_on(engine.parse_metadata(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(meta.diagram_type, expected, "{text:?}");
+
+        let parsed = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.meta.diagram_type, expected, "{text:?}");
+    }
+}
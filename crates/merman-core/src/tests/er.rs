@@ -45,6 +45,30 @@ fn parse_diagram_er_empty_quoted_entity_name_is_error() {
     assert!(err.contains("DiagramParse") || err.contains("Unsupported") || !err.is_empty());
 }
 
+#[test]
+fn parse_diagram_er_empty_quoted_alias_is_error_and_missing_alias_is_empty_string() {
+    // Mermaid parses the alias with the same non-empty `"[^"...]+"` entity-name token, so
+    // `foo[""]` is rejected just like an empty quoted entity name. An entity without an alias
+    // reports `alias: ""`, never `null`.
+    let engine = Engine::new();
+    for text in ["erDiagram\nfoo[\"\"]\n", "erDiagram\nfoo[\"\"] {\n}\n"] {
+        assert!(
+            block_on(engine.parse_diagram(text, ParseOptions::default())).is_err(),
+            "{text}"
+        );
+    }
+
+    let res = block_on(engine.parse_diagram(
+        "erDiagram\nfoo[\"Foo\"]\nbar\nfoo ||--o{ baz : has\n",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(res.model["entities"]["foo"]["alias"], json!("Foo"));
+    assert_eq!(res.model["entities"]["bar"]["alias"], json!(""));
+    assert_eq!(res.model["entities"]["baz"]["alias"], json!(""));
+}
+
 #[test]
 fn parse_diagram_er_rejects_percent_and_backslash_in_quoted_entity_name() {
     let engine = Engine::new();
@@ -123,6 +147,35 @@ NODE ||--o{ NODE : "leads to"
     assert_eq!(rels[0]["entityA"], rels[0]["entityB"]);
 }
 
+#[test]
+fn parse_diagram_er_relationships_flag_self_loops() {
+    let engine = Engine::new();
+    let text = r#"erDiagram
+NODE ||--o{ NODE : "leads to"
+NODE ||--|{ EDGE : has
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let rels = res.model["relationships"].as_array().unwrap();
+    assert_eq!(rels[0]["isSelf"], json!(true));
+    assert!(rels[1].get("isSelf").is_none());
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Er(model) = parsed.model else {
+        panic!("er render parse should return typed model");
+    };
+    let flags = model
+        .relationships
+        .iter()
+        .map(|r| r.is_self)
+        .collect::<Vec<_>>();
+    assert_eq!(flags, vec![true, false]);
+}
+
 #[test]
 fn parse_diagram_er_inline_class_assignment_applies_css_classes() {
     let engine = Engine::new();
@@ -598,6 +651,29 @@ classDef myClass fill:#f9f, stroke: red, color: pink
     );
 }
 
+#[test]
+fn parse_diagram_er_classdef_and_style_drop_all_whitespace_like_mermaid() {
+    // Mermaid's ER lexer skips whitespace inside style statements, so even spaces within a
+    // value disappear (`1px solid red` -> `1pxsolidred`).
+    let engine = Engine::new();
+    let text = r#"erDiagram
+A
+classDef foo background: #bbb , border:1px solid red
+style A fill: #f9f ,stroke: #333
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["classes"]["foo"]["styles"],
+        json!(["background:#bbb", "border:1pxsolidred"])
+    );
+    assert_eq!(
+        res.model["entities"]["A"]["cssStyles"],
+        json!(["fill:#f9f", "stroke:#333"])
+    );
+}
+
 #[test]
 fn parse_diagram_er_classdef_supports_multiple_classes_in_one_statement() {
     let engine = Engine::new();
@@ -777,6 +853,41 @@ A ||--o{ B : has
     assert_eq!(res.model["accDescr"], json!("this graph is\nabout\nstuff"));
 }
 
+#[test]
+fn parse_diagram_er_acc_title_and_single_line_description() {
+    let engine = Engine::new();
+    let text = r#"erDiagram
+accTitle: graph title
+accDescr: one liner
+A ||--o{ B : has
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.model["accTitle"], json!("graph title"));
+    assert_eq!(res.model["accDescr"], json!("one liner"));
+    assert_eq!(res.model["relationships"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn parse_diagram_er_single_line_and_block_descriptions_share_sanitization() {
+    let engine = Engine::new();
+    let single = block_on(engine.parse_diagram(
+        "erDiagram\naccDescr: <b>bold</b><script>alert(1)</script>\nA ||--o{ B : has\n",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    let block = block_on(engine.parse_diagram(
+        "erDiagram\naccDescr {\n<b>bold</b><script>alert(1)</script>\n}\nA ||--o{ B : has\n",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(single.model["accDescr"], json!("<b>bold</b>"));
+    assert_eq!(single.model["accDescr"], block.model["accDescr"]);
+}
+
 #[test]
 fn parse_diagram_er_multibyte_attribute_does_not_panic() {
     let engine = Engine::new();
@@ -951,3 +1062,146 @@ fn parse_er_editor_facts_record_expected_id_list_spans() {
             && expected.span.start == text.find("pink").unwrap()
     }));
 }
+
+#[test]
+fn parse_diagram_er_attribute_type_validation_is_opt_in() {
+    let engine = Engine::new();
+    let body = r#"erDiagram
+CUSTOMER {
+  character(10) code
+  decimal(10 amount
+}
+"#;
+
+    let res = block_on(engine.parse_diagram(body, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert!(res.model.get("warningFacts").is_none());
+    assert!(res.model.get("warnings").is_none());
+    assert_eq!(
+        res.model["entities"]["CUSTOMER"]["attributes"][1]["type"],
+        json!("decimal(10")
+    );
+}
+
+#[test]
+fn parse_diagram_er_attribute_type_validation_warns_on_unrecognized_types() {
+    let engine = Engine::new();
+    let text = r#"%%{init: {"er": {"validateAttributeTypes": true}}}%%
+erDiagram
+CUSTOMER {
+  character(10) code
+  decimal(10,2) amount
+  string[] tags
+  int[4] slots
+  list~string~ names
+  map~string,int~ counts
+  type~T~ value
+  decimal(10 broken
+  int)( flipped
+}
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        res.model["warningFacts"],
+        json!([
+            {
+                "ruleId": ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID,
+                "message": "attribute `broken` on entity `CUSTOMER` has unrecognized type `decimal(10`"
+            },
+            {
+                "ruleId": ER_UNRECOGNIZED_ATTRIBUTE_TYPE_WARNING_RULE_ID,
+                "message": "attribute `flipped` on entity `CUSTOMER` has unrecognized type `int)(`"
+            }
+        ])
+    );
+    assert_eq!(res.model["warnings"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        res.model["entities"]["CUSTOMER"]["attributes"]
+            .as_array()
+            .unwrap()
+            .len(),
+        9
+    );
+}
+
+#[test]
+fn parse_diagram_er_rejects_attribute_types_starting_with_a_digit() {
+    // Mermaid's ATTRIBUTE_WORD must start with a letter, `_`, or `*`, so `123bad` is a parse error
+    // before the opt-in type validation ever sees it.
+    let engine = Engine::new();
+    let text = r#"%%{init: {"er": {"validateAttributeTypes": true}}}%%
+erDiagram
+CUSTOMER {
+  123bad code
+}
+"#;
+    assert!(block_on(engine.parse_diagram(text, ParseOptions::default())).is_err());
+}
+
+#[test]
+fn parse_diagram_er_attribute_keys_expose_boolean_flags() {
+    let engine = Engine::new();
+    let text = "erDiagram\nCUSTOMER {\n  string email PK, UK\n  int region FK\n  string name\n}\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let attrs = &res.model["entities"]["CUSTOMER"]["attributes"];
+    assert_eq!(attrs[0]["keys"], json!(["PK", "UK"]));
+    assert_eq!(attrs[0]["isPrimaryKey"], json!(true));
+    assert_eq!(attrs[0]["isUniqueKey"], json!(true));
+    assert!(attrs[0].get("isForeignKey").is_none());
+    assert_eq!(attrs[1]["isForeignKey"], json!(true));
+    assert!(attrs[1].get("isPrimaryKey").is_none());
+    assert!(attrs[2].get("isPrimaryKey").is_none());
+
+    let RenderSemanticModel::Er(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected er render model");
+    };
+    let email = &model.entities["CUSTOMER"].attributes[0];
+    assert!(email.is_primary_key && email.is_unique_key && !email.is_foreign_key);
+}
+
+#[test]
+fn parse_diagram_er_relationship_label_is_sanitized() {
+    let text = "erDiagram\nA ||--o{ B : \"has <script>alert(1)</script>\"\n";
+
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let role = res.model["relationships"][0]["roleA"].as_str().unwrap();
+    assert!(role.starts_with("has"), "roleA: {role}");
+    assert!(!role.contains("<script"), "roleA: {role}");
+    assert!(!role.contains("alert(1)"), "roleA: {role}");
+
+    let RenderSemanticModel::Er(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected er render model");
+    };
+    assert_eq!(model.relationships[0].role_a, role);
+
+    let res = block_on(engine.parse_diagram(
+        "erDiagram\nA ||--o{ B : \"a <b>bold</b> role\"\n",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        res.model["relationships"][0]["roleA"],
+        json!("a <b>bold</b> role")
+    );
+}
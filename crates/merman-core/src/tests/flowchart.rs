@@ -67,6 +67,46 @@ fn parse_swimlane_reuses_flowchart_semantics_and_editor_facts() {
     assert_eq!(a.selection.end, a_start + "A".len());
 }
 
+#[test]
+fn parse_diagram_flowchart_elk_records_flowchart_keyword() {
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram("flowchart-elk TD\nA-->B", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(res.meta.diagram_type, "flowchart-elk");
+    assert_eq!(res.meta.effective_config.get_str("layout"), Some("elk"));
+    assert_eq!(res.model["keyword"], json!("flowchart"));
+    assert_eq!(res.model["direction"], json!("TB"));
+}
+
+#[test]
+fn parse_diagram_flowchart_keyword_is_the_header_as_written() {
+    // Mermaid's flowchart detectors (`/^\s*graph/`, `/^\s*flowchart/`) are case-sensitive, so
+    // `keyword` never needs case folding: it is the header keyword exactly as written, and
+    // mixed-case headers are not flowcharts at all. The only rewrite is `flowchart-elk`, which
+    // stays recoverable through `type`.
+    let engine = Engine::new();
+    for (text, keyword, ty) in [
+        ("graph TD\nA-->B", "graph", "flowchart-v2"),
+        ("flowchart TD\nA-->B", "flowchart", "flowchart-v2"),
+        ("flowchart-elk TD\nA-->B", "flowchart", "flowchart-elk"),
+    ] {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.model["keyword"], json!(keyword), "{text}");
+        assert_eq!(res.model["type"], json!(ty), "{text}");
+    }
+
+    for text in ["Flowchart TD\nA-->B", "GRAPH TD\nA-->B"] {
+        assert!(
+            block_on(engine.parse_diagram(text, ParseOptions::default())).is_err(),
+            "{text}"
+        );
+    }
+}
+
 #[test]
 fn parse_swimlane_layout_default_respects_user_config_precedence() {
     let engine = Engine::new().with_site_config(MermaidConfig::from_value(json!({
@@ -974,6 +1014,52 @@ fn parse_diagram_flowchart_node_data_shape_validation_errors() {
     );
 }
 
+#[test]
+fn flowchart_canonical_shape_resolves_aliases_and_rejects_unknown_names() {
+    use crate::diagrams::flowchart::{canonical_shape, flowchart_public_shape_names};
+
+    for (name, canonical) in [
+        ("rect", "rect"),
+        ("process", "rect"),
+        ("database", "cyl"),
+        ("db", "cyl"),
+        ("decision", "diam"),
+        ("terminal", "stadium"),
+        ("trapezoid", "trap-b"),
+        ("inv-trapezoid", "trap-t"),
+        ("subroutine", "fr-rect"),
+        ("circ", "circle"),
+        ("junction", "f-circ"),
+        ("doublecircle", "doublecircle"),
+        ("text", "text"),
+    ] {
+        assert_eq!(canonical_shape(name), Some(canonical), "shape: {name}");
+    }
+    for name in [
+        "this-shape-does-not-exist",
+        "forkJoin",
+        "rect_left_inv_arrow",
+    ] {
+        assert_eq!(canonical_shape(name), None, "shape: {name}");
+    }
+    for name in flowchart_public_shape_names() {
+        let canonical = canonical_shape(name).expect("public shape has a canonical name");
+        assert_eq!(canonical_shape(canonical), Some(canonical), "shape: {name}");
+    }
+
+    let engine = Engine::new();
+    let err = block_on(engine.parse_diagram(
+        "flowchart TB\nA@{ shape: lightningBolt }",
+        ParseOptions::default(),
+    ))
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("No such shape: lightningBolt. Shape names should be lowercase."),
+        "error: {err}"
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_node_data_multiline_strings_match_mermaid() {
     let engine = Engine::new();
@@ -1125,6 +1211,28 @@ A1 e1@--> C1 & D1
     assert_eq!(edges[5]["id"], json!("L_A1_D1_0"));
 }
 
+#[test]
+fn parse_diagram_flowchart_reused_edge_id_across_statements_becomes_auto_id() {
+    let engine = Engine::new();
+
+    let text = r#"flowchart TD
+A e1@--> B
+C e1@--> D
+C --> D
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let edges = res.model["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 3);
+    assert_eq!(edges[0]["id"], json!("e1"));
+    assert_eq!(edges[0]["isUserDefinedId"], json!(true));
+    assert_eq!(edges[1]["id"], json!("L_C_D_0"));
+    assert_eq!(edges[1]["isUserDefinedId"], json!(false));
+    assert_eq!(edges[2]["id"], json!("L_C_D_2"));
+    assert_eq!(edges[2]["isUserDefinedId"], json!(false));
+}
+
 #[test]
 fn parse_diagram_flowchart_node_data_overrides_edge_animate() {
     let engine = Engine::new();
@@ -1151,6 +1259,72 @@ e3@{ animate: false }
     assert_eq!(edges[2]["animate"], json!(false));
 }
 
+#[test]
+fn parse_diagram_flowchart_redeclared_node_label_and_shape_follow_add_vertex_precedence() {
+    // Mermaid's `addVertex` overwrites text/type whenever a statement supplies them, and leaves
+    // them alone for bare references, so the last explicit declaration wins.
+    let engine = Engine::new();
+    let text = r#"flowchart TD
+A["first"] --> B
+A("second")
+A --> C
+B{"b label"}
+B@{ shape: hex }
+C[C label]
+C@{ label: "from data" }
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let nodes = res.model["nodes"].as_array().unwrap();
+    assert_eq!(nodes.len(), 3);
+
+    assert_eq!(nodes[0]["id"], json!("A"));
+    assert_eq!(nodes[0]["label"], json!("second"));
+    assert_eq!(nodes[0]["layoutShape"], json!("roundedRect"));
+
+    assert_eq!(nodes[1]["id"], json!("B"));
+    assert_eq!(nodes[1]["label"], json!("b label"));
+    assert_eq!(nodes[1]["layoutShape"], json!("hex"));
+
+    assert_eq!(nodes[2]["id"], json!("C"));
+    assert_eq!(nodes[2]["label"], json!("from data"));
+    assert_eq!(nodes[2]["layoutShape"], json!("squareRect"));
+}
+
+#[test]
+fn parse_diagram_flowchart_capitalized_end_and_end_prefixed_ids_are_nodes() {
+    // Only lowercase `end` at a word boundary is the subgraph terminator (`"end"\b` in Mermaid's
+    // lexer); `End`, `END`, and ids that merely start with `end` are ordinary vertices.
+    let engine = Engine::new();
+    let text = "flowchart TD\nA-->End\nB-->END\nsubgraph S\nC-->end2\nendpoint\nend\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let node_ids = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["id"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(node_ids, ["A", "End", "B", "END", "C", "end2", "endpoint"]);
+    assert_eq!(res.model["edges"][0]["to"], json!("End"));
+    assert_eq!(res.model["edges"][1]["to"], json!("END"));
+
+    let subgraphs = res.model["subgraphs"].as_array().unwrap();
+    assert_eq!(subgraphs.len(), 1);
+    assert_eq!(subgraphs[0]["id"], json!("S"));
+    assert_eq!(subgraphs[0]["nodes"], json!(["end2", "C", "endpoint"]));
+}
+
+#[test]
+fn parse_diagram_flowchart_lowercase_end_as_edge_target_is_rejected() {
+    let engine = Engine::new();
+    let text = "flowchart TD\nA-->end\n";
+    assert!(block_on(engine.parse_diagram(text, ParseOptions::default())).is_err());
+}
+
 #[test]
 fn parse_diagram_flowchart_markdown_strings_in_nodes_and_edges() {
     let engine = Engine::new();
@@ -1183,6 +1357,24 @@ fn parse_diagram_flowchart_markdown_strings_in_nodes_and_edges() {
     assert_eq!(edges[1]["labelType"], json!("string"));
 }
 
+#[test]
+fn parse_diagram_flowchart_markdown_strings_in_pipe_edge_labels() {
+    let engine = Engine::new();
+    let text = "flowchart\nA-->|\"`**x**`\"|B\nB-->|`**y**`|C";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let edges = res.model["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges[0]["label"], json!("**x**"));
+    assert_eq!(edges[0]["labelType"], json!("markdown"));
+    // Mermaid only enters markdown mode for pipe labels via a quoted `MD_STR`; bare backticks
+    // stay literal text.
+    assert_eq!(edges[1]["label"], json!("`**y**`"));
+    assert_eq!(edges[1]["labelType"], json!("text"));
+}
+
 #[test]
 fn parse_diagram_flowchart_plain_node_labels_can_span_indented_lines() {
     let engine = Engine::new();
@@ -1815,6 +2007,50 @@ fn parse_diagram_flowchart_supports_subgraph_block() {
     );
 }
 
+#[test]
+fn parse_diagram_flowchart_keeps_empty_subgraphs() {
+    let engine = Engine::new();
+    let text = "flowchart TD\nsubgraph S\nend\nsubgraph lane2[\"Lane 2\"]\nend\nA-->B\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["subgraphs"],
+        json!([{
+            "id": "S",
+            "nodes": [],
+            "title": "S",
+            "classes": [],
+            "styles": [],
+            "dir": null,
+            "hasExplicitDir": false,
+            "labelType": "text"
+        }, {
+            "id": "lane2",
+            "nodes": [],
+            "title": "Lane 2",
+            "classes": [],
+            "styles": [],
+            "dir": null,
+            "hasExplicitDir": false,
+            "labelType": "string"
+        }])
+    );
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    match parsed.model {
+        RenderSemanticModel::Flowchart(model) => {
+            let ids: Vec<&str> = model.subgraphs.iter().map(|s| s.id.as_str()).collect();
+            assert_eq!(ids, vec!["S", "lane2"]);
+            assert!(model.subgraphs.iter().all(|s| s.nodes.is_empty()));
+        }
+        other => panic!("flowchart render parse should return typed model, got {other:?}"),
+    }
+}
+
 #[test]
 fn parse_diagram_flowchart_supports_nested_subgraphs() {
     let engine = Engine::new();
@@ -2006,6 +2242,140 @@ fn parse_diagram_flowchart_subgraph_markdown_title_sets_label_type_markdown() {
     assert_eq!(res.model["subgraphs"][0]["labelType"], json!("markdown"));
 }
 
+#[test]
+fn parse_diagram_flowchart_subgraph_explicit_id_markdown_title_sets_label_type_markdown() {
+    let engine = Engine::new();
+    for text in [
+        "graph TD\nsubgraph uid[\"`**M**`\"]\nA-->B\nend",
+        "graph TD;subgraph uid[\"`**M**`\"];A-->B;end;",
+    ] {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        let subgraph = &res.model["subgraphs"][0];
+        assert_eq!(subgraph["id"], json!("uid"), "diagram: {text}");
+        assert_eq!(subgraph["title"], json!("**M**"), "diagram: {text}");
+        assert_eq!(subgraph["labelType"], json!("markdown"), "diagram: {text}");
+    }
+}
+
+#[test]
+fn parse_diagram_flowchart_duplicate_explicit_subgraph_id_is_an_error() {
+    let engine = Engine::new();
+    for text in [
+        "flowchart TB\nsubgraph S\nA\nend\nsubgraph S\nB\nend",
+        "flowchart TB\nsubgraph S[One]\nA\nend\nsubgraph S[Two]\nB\nend",
+        "flowchart TB\nsubgraph S\nsubgraph S\nA\nend\nend",
+    ] {
+        let err = block_on(engine.parse_diagram(text, ParseOptions::default())).unwrap_err();
+        assert!(
+            err.to_string().contains("Duplicate subgraph id: S."),
+            "diagram: {text}\nerror: {err}"
+        );
+    }
+
+    // Titles with whitespace get generated ids, so repeating them is fine.
+    let res = block_on(engine.parse_diagram(
+        "flowchart TB\nsubgraph Same title\nA\nend\nsubgraph Same title\nB\nend",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    let ids: Vec<_> = res.model["subgraphs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|sg| sg["id"].clone())
+        .collect();
+    assert_eq!(ids, vec![json!("subGraph0"), json!("subGraph1")]);
+}
+
+#[test]
+fn parse_diagram_flowchart_spans_map_node_and_edge_ids_to_source() {
+    let engine = Engine::new();
+    let text = "%%{init: {\"theme\": \"dark\"}}%%\nflowchart TD\n%% start\nA[Start] -->|go| B & C\nB e1@--> D\nD --- A\n";
+    let parsed = block_on(engine.parse_diagram(text, ParseOptions::strict().with_spans()))
+        .unwrap()
+        .unwrap();
+    let spans = parsed.spans.expect("spans were requested");
+    let source = |range: &std::ops::Range<usize>| &text[range.clone()];
+
+    // Nodes point at the id where they first appear.
+    let node_ids: Vec<_> = parsed.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(spans.nodes.len(), node_ids.len());
+    for id in node_ids {
+        assert_eq!(source(&spans.nodes[id]), id);
+    }
+    assert_eq!(spans.nodes["A"].start, text.find("A[Start]").unwrap());
+
+    // Edges point at their link, including an edge id prefix and the label.
+    assert_eq!(source(&spans.edges["L_A_B_0"]), "-->|go|");
+    assert_eq!(source(&spans.edges["L_A_C_0"]), "-->|go|");
+    assert_eq!(source(&spans.edges["e1"]), "e1@-->");
+    assert_eq!(source(&spans.edges["L_D_A_0"]), "---");
+    assert!(spans.messages.is_empty());
+
+    let without = block_on(engine.parse_diagram(text, ParseOptions::strict()))
+        .unwrap()
+        .unwrap();
+    assert!(without.spans.is_none());
+    assert_eq!(without.model, parsed.model);
+}
+
+#[test]
+fn parse_diagram_flowchart_generated_subgraph_ids_skip_ids_in_use() {
+    let engine = Engine::new();
+    let subgraph_ids = |text: &str| {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        res.model["subgraphs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|sg| sg["id"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // A node already named `subGraph0` pushes the generated id along.
+    assert_eq!(
+        subgraph_ids("flowchart TB\nsubGraph0 --> B\nsubgraph My group\nA\nend"),
+        vec!["subGraph1"]
+    );
+    // So does an explicit subgraph id, even one declared later in the source.
+    assert_eq!(
+        subgraph_ids(
+            "flowchart TB\nsubgraph First group\nA\nend\nsubgraph Second group\nB\nend\nsubgraph subGraph1\nC\nend"
+        ),
+        vec!["subGraph0", "subGraph2", "subGraph1"]
+    );
+    // Without a collision the ids keep Mermaid's numbering.
+    assert_eq!(
+        subgraph_ids("flowchart TB\nsubgraph One a\nA\nend\nsubgraph Two b\nB\nend"),
+        vec!["subGraph0", "subGraph1"]
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_class_statement_targets_generated_subgraph_id() {
+    // `class`/`style`/`click` targets refer to a generated id; they must not push it along.
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram(
+        "flowchart TB\nsubgraph My group\nA\nend\nclassDef hot fill:#f00\nclass subGraph0 hot",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    let sg = &res.model["subgraphs"][0];
+    assert_eq!(sg["id"], json!("subGraph0"));
+    assert_eq!(sg["classes"], json!(["hot"]));
+}
+
 #[test]
 fn parse_diagram_flowchart_duplicate_subgraph_membership_matches_mermaid_makeuniq() {
     let engine = Engine::new();
@@ -2038,6 +2408,21 @@ fn parse_diagram_flowchart_duplicate_subgraph_membership_matches_mermaid_makeuni
     );
 }
 
+#[test]
+fn parse_diagram_flowchart_subgraph_amp_group_chains_keep_mermaid_member_order() {
+    // Each link step prepends its right-hand group, and `addSubGraph` keeps the first occurrence
+    // of every id across the flattened statements.
+    let engine = Engine::new();
+    let text = "graph TD\nsubgraph S\nx\na & b --> c & d --> e\nd --> x & f\nend";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["subgraphs"][0]["nodes"],
+        json!(["x", "e", "c", "d", "a", "b", "f"])
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_subgraph_supports_amp_group_syntax_minimally() {
     let engine = Engine::new();
@@ -2045,15 +2430,12 @@ fn parse_diagram_flowchart_subgraph_supports_amp_group_syntax_minimally() {
     let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
         .unwrap()
         .unwrap();
-    let nodes = res.model["subgraphs"][0]["nodes"].as_array().unwrap();
-    let as_set: std::collections::HashSet<String> = nodes
-        .iter()
-        .map(|v| v.as_str().unwrap().to_string())
-        .collect();
-    assert!(as_set.contains("a"));
-    assert!(as_set.contains("b"));
-    assert!(as_set.contains("c"));
-    assert!(as_set.contains("e"));
+    // Mermaid's `vertexStatement link node` rule yields `nodes: $3.concat($1.nodes)`, so the
+    // right-hand group comes first.
+    assert_eq!(
+        res.model["subgraphs"][0]["nodes"],
+        json!(["c", "e", "a", "b"])
+    );
 }
 
 #[test]
@@ -2119,40 +2501,194 @@ fn parse_diagram_flowchart_classdef_and_class_assign_work() {
 #[test]
 fn parse_diagram_flowchart_inline_vertex_class_via_style_separator() {
     let engine = Engine::new();
-    // Mermaid `encodeEntities(...)` treats `#bbb;` as an entity placeholder when semicolons
-    // are used as statement separators. Use newlines to match upstream parsing behavior.
-    let text = "graph TD\nclassDef exClass background:#bbb\nA-->B[test]:::exClass\n";
-    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
-        .unwrap()
-        .unwrap();
-    let b = res.model["nodes"]
-        .as_array()
-        .unwrap()
-        .iter()
-        .find(|n| n["id"] == json!("B"))
-        .unwrap();
-    assert_eq!(b["classes"][0], json!("exClass"));
+    for text in [
+        "graph TD\nclassDef exClass background:#bbb\nA-->B[test]:::exClass\n",
+        "graph TD;classDef exClass background:#bbb;A-->B[test]:::exClass",
+    ] {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        let b = res.model["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["id"] == json!("B"))
+            .unwrap();
+        assert_eq!(b["classes"][0], json!("exClass"), "diagram: {text}");
+        assert_eq!(
+            res.model["classDefs"]["exClass"],
+            json!(["background:#bbb"]),
+            "diagram: {text}"
+        );
+    }
 }
 
 #[test]
-fn parse_diagram_flowchart_linkstyle_applies_edge_style_and_validates_bounds() {
+fn parse_diagram_flowchart_styles_pass_through_important_and_vendor_prefixes() {
     let engine = Engine::new();
-    let ok = "graph TD\nA-->B\nlinkStyle 0 stroke-width:1px;";
-    let res = block_on(engine.parse_diagram(ok, ParseOptions::default()))
+    let text = "graph TD
+A-->B
+style A color:red !important,-webkit-text-stroke:1px black;
+linkStyle 0 stroke:blue !important,-moz-opacity:0.5
+classDef c color:red !important,-webkit-filter:blur(1px)
+";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
         .unwrap()
         .unwrap();
-    assert_eq!(res.model["edges"][0]["style"][0], json!("stroke-width:1px"));
-
-    let bad = "graph TD\nA-->B\nlinkStyle 1 stroke-width:1px;";
-    let err = block_on(engine.parse_diagram(bad, ParseOptions::default())).unwrap_err();
     assert_eq!(
-        err.to_string(),
-        "Diagram parse error (flowchart-v2): The index 1 for linkStyle is out of bounds. Valid indices for linkStyle are between 0 and 0. (Help: Ensure that the index is within the range of existing edges.)"
+        res.model["nodes"][0]["styles"],
+        json!(["color:red !important", "-webkit-text-stroke:1px black"])
+    );
+    let edge_style = res.model["edges"][0]["style"].as_array().unwrap();
+    assert_eq!(
+        edge_style[..2],
+        [json!("stroke:blue !important"), json!("-moz-opacity:0.5")]
+    );
+    assert_eq!(
+        res.model["classDefs"]["c"],
+        json!(["color:red !important", "-webkit-filter:blur(1px)"])
     );
 }
 
 #[test]
-fn parse_diagram_flowchart_linkstyle_default_interpolate_sets_edge_defaults() {
+fn parse_diagram_flowchart_semicolon_separated_hex_styles_keep_their_colors() {
+    let engine = Engine::new();
+    let node_styles = |text: &str| {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        res.model["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| (n["id"].clone(), n["styles"].clone(), n["classes"].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        node_styles("graph TD;Q;style Q fill:#bbb;"),
+        vec![(json!("Q"), json!(["fill:#bbb"]), json!([]))]
+    );
+
+    let newline =
+        node_styles("graph TD\nA-->B\nstyle A fill:#bbb\nstyle B fill:#ccc,stroke:#333\n");
+    let semicolon = node_styles("graph TD;A-->B;style A fill:#bbb;style B fill:#ccc,stroke:#333;");
+    assert_eq!(semicolon, newline);
+    assert_eq!(
+        semicolon,
+        vec![
+            (json!("A"), json!(["fill:#bbb"]), json!([])),
+            (json!("B"), json!(["fill:#ccc", "stroke:#333"]), json!([])),
+        ]
+    );
+
+    let text = "graph TD;A-->B;classDef a fill:#bbb;classDef b fill:#ccc;class A a;class B b";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["classDefs"],
+        json!({ "a": ["fill:#bbb"], "b": ["fill:#ccc"] })
+    );
+    assert_eq!(
+        node_styles(text)
+            .into_iter()
+            .map(|(_, _, classes)| classes)
+            .collect::<Vec<_>>(),
+        vec![json!(["a"]), json!(["b"])]
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_linkstyle_applies_edge_style_and_validates_bounds() {
+    let engine = Engine::new();
+    let ok = "graph TD\nA-->B\nlinkStyle 0 stroke-width:1px;";
+    let res = block_on(engine.parse_diagram(ok, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.model["edges"][0]["style"][0], json!("stroke-width:1px"));
+
+    let bad = "graph TD\nA-->B\nlinkStyle 1 stroke-width:1px;";
+    let err = block_on(engine.parse_diagram(bad, ParseOptions::default())).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Diagram parse error (flowchart-v2): The index 1 for linkStyle is out of bounds. Valid indices for linkStyle are between 0 and 0. (Help: Ensure that the index is within the range of existing edges.)"
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_linkstyle_rejects_negative_index() {
+    // Mermaid's grammar only accepts `NUM` tokens in the position list, so `-1` is a syntax
+    // error rather than a from-the-end index.
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nlinkStyle -1 stroke:red;";
+    let err = block_on(engine.parse_diagram(text, ParseOptions::default())).unwrap_err();
+    assert!(
+        err.to_string().contains("Invalid linkStyle statement"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_linkstyle_duplicate_index_applies_style_once() {
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nA-->C\nlinkStyle 0,0 stroke:red;";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["edges"][0]["style"],
+        json!(["stroke:red", "fill:none"])
+    );
+    assert_eq!(res.model["edges"][1]["style"], json!([]));
+}
+
+#[test]
+fn parse_diagram_flowchart_linkstyle_default_and_numbered_styles_compose() {
+    // `default` only fills `edgeDefaults`; numbered positions set the edge's own style, which
+    // renderers apply after the defaults. Statement order does not matter.
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nA-->C\nlinkStyle 1 stroke:blue;\nlinkStyle default stroke:red,stroke-width:2px;";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["edgeDefaults"]["style"],
+        json!(["stroke:red", "stroke-width:2px"])
+    );
+    assert_eq!(res.model["edges"][0]["style"], json!([]));
+    assert_eq!(
+        res.model["edges"][1]["style"],
+        json!(["stroke:blue", "fill:none"])
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_style_lists_split_only_on_commas() {
+    // Space-separated values belong to one declaration; only commas separate declarations.
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nlinkStyle 0 stroke-dasharray:5 5,stroke:1px solid red\nstyle A stroke-dasharray:5 5,stroke:1px solid red";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["edges"][0]["style"],
+        json!(["stroke-dasharray:5 5", "stroke:1px solid red", "fill:none"])
+    );
+    let a = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == json!("A"))
+        .unwrap();
+    assert_eq!(
+        a["styles"],
+        json!(["stroke-dasharray:5 5", "stroke:1px solid red"])
+    );
+}
+
+#[test]
+fn parse_diagram_flowchart_linkstyle_default_interpolate_sets_edge_defaults() {
     let engine = Engine::new();
     let text = "graph TD\nA-->B\nlinkStyle default interpolate basis";
     let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
@@ -2210,6 +2746,24 @@ fn parse_diagram_flowchart_edge_curve_properties_does_not_override_default() {
     assert_eq!(res.model["edges"][0]["interpolate"], json!("stepAfter"));
 }
 
+#[test]
+fn parse_diagram_flowchart_edge_curve_rejects_unknown_curve_name() {
+    let engine = Engine::new();
+    let ok = "graph TD\nA e1@-->B\ne1@{curve: catmullRom}";
+    let res = block_on(engine.parse_diagram(ok, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.model["edges"][0]["interpolate"], json!("catmullRom"));
+
+    let bad = "graph TD\nA e1@-->B\ne1@{curve: wobble}";
+    let err = block_on(engine.parse_diagram(bad, ParseOptions::default())).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid curve \"wobble\" for edge e1. Valid curves are: basis,"),
+        "unexpected error: {err}"
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_edge_curve_properties_mixed_with_line_interpolation() {
     let engine = Engine::new();
@@ -2223,6 +2777,30 @@ fn parse_diagram_flowchart_edge_curve_properties_mixed_with_line_interpolation()
     assert_eq!(res.model["edges"][3]["interpolate"], json!("stepBefore"));
 }
 
+#[test]
+fn parse_diagram_flowchart_numbered_link_style_and_edge_curve_last_statement_wins() {
+    let engine = Engine::new();
+    let interpolate = |text: &str| {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        res.model["edges"][0]["interpolate"].clone()
+    };
+
+    assert_eq!(
+        interpolate(
+            "graph TD\nA e1@-->B\nlinkStyle default interpolate linear\ne1@{curve: cardinal}\nlinkStyle 0 interpolate basis"
+        ),
+        json!("basis")
+    );
+    assert_eq!(
+        interpolate(
+            "graph TD\nA e1@-->B\nlinkStyle 0 interpolate basis\ne1@{curve: cardinal}\nlinkStyle default interpolate linear"
+        ),
+        json!("cardinal")
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_click_link_sets_link_and_tooltip_and_clickable_class() {
     let engine = Engine::new();
@@ -2242,6 +2820,58 @@ fn parse_diagram_flowchart_click_link_sets_link_and_tooltip_and_clickable_class(
     assert_eq!(a["classes"][0], json!("clickable"));
 }
 
+#[test]
+fn parse_diagram_flowchart_click_link_target_omitted_invalid_and_sandbox() {
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nclick A href \"click.html\" \"tooltip\"";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let a = &res.model["nodes"][0];
+    assert_eq!(a["link"], json!("click.html"));
+    assert_eq!(a["linkTarget"], json!(null));
+
+    let err = block_on(engine.parse_diagram(
+        "graph TD\nA-->B\nclick A href \"click.html\" \"tooltip\" _bogus",
+        ParseOptions::default(),
+    ))
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("unknown link target _bogus"),
+        "unexpected error: {err}"
+    );
+
+    let sandbox = Engine::new().with_site_config({
+        let mut cfg = MermaidConfig::empty_object();
+        cfg.set_value("securityLevel", json!("sandbox"));
+        cfg
+    });
+    let text = "graph TD\nA-->B\nclick A href \"click.html\" _blank\nclick B call cb()";
+    let parsed = sandbox
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Flowchart(model) = parsed.model else {
+        panic!("expected typed flowchart render model");
+    };
+    let node = |id: &str| model.nodes.iter().find(|n| n.id == id).unwrap();
+    assert_eq!(node("A").link_target.as_deref(), Some("_blank"));
+    assert_eq!(node("A").link_target_effective.as_deref(), Some("_top"));
+    assert_eq!(node("B").link_target, None);
+    assert_eq!(node("B").link_target_effective, None);
+
+    let parsed = Engine::new()
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Flowchart(model) = parsed.model else {
+        panic!("expected typed flowchart render model");
+    };
+    let a = model.nodes.iter().find(|n| n.id == "A").unwrap();
+    assert_eq!(a.link_target.as_deref(), Some("_blank"));
+    assert_eq!(a.link_target_effective.as_deref(), Some("_blank"));
+}
+
 #[test]
 fn parse_diagram_flowchart_click_link_sanitizes_javascript_urls_when_not_loose() {
     let engine = Engine::new();
@@ -2259,6 +2889,120 @@ fn parse_diagram_flowchart_click_link_sanitizes_javascript_urls_when_not_loose()
     assert_eq!(a["linkTarget"], json!("_blank"));
 }
 
+#[test]
+fn parse_diagram_flowchart_click_call_parses_callback_args_without_loose_security() {
+    let engine = Engine::new();
+    let text = "graph TD\nA-->B\nclick A call myFn(1, \"two\", three) \"tooltip\"";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let a = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == json!("A"))
+        .unwrap();
+    assert_eq!(a["haveCallback"], json!(false));
+    assert_eq!(a["callback"]["function"], json!("myFn"));
+    assert_eq!(a["callback"]["args"], json!(["1", "two", "three"]));
+    assert_eq!(a["callbackEffective"], json!(false));
+    assert_eq!(a["classes"][0], json!("clickable"));
+    assert_eq!(res.model["tooltips"]["A"], json!("tooltip"));
+
+    let b = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == json!("B"))
+        .unwrap();
+    assert!(b.get("callback").is_none());
+}
+
+#[test]
+fn parse_diagram_flowchart_click_call_and_href_on_one_node_both_populate() {
+    // Mermaid's `setLink` and `setClickEvent` are independent: both fields are kept whichever
+    // statement comes first, and each pushes its own `clickable` class.
+    let loose = Engine::new().with_site_config({
+        let mut cfg = MermaidConfig::empty_object();
+        cfg.set_value("securityLevel", json!("loose"));
+        cfg
+    });
+    for (engine, have_callback) in [(Engine::new(), false), (loose, true)] {
+        for text in [
+            "flowchart TD\nA-->B\nclick A call cb()\nclick A href \"https://example.com\" _blank",
+            "flowchart TD\nA-->B\nclick A href \"https://example.com\" _blank\nclick A call cb()",
+        ] {
+            let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+                .unwrap()
+                .unwrap();
+            let a = &res.model["nodes"][0];
+            assert_eq!(a["id"], json!("A"));
+            assert!(
+                a["link"]
+                    .as_str()
+                    .is_some_and(|link| link.starts_with("https://example.com")),
+                "diagram: {text}"
+            );
+            assert_eq!(a["linkTarget"], json!("_blank"), "diagram: {text}");
+            assert_eq!(a["callback"]["function"], json!("cb"), "diagram: {text}");
+            assert_eq!(a["haveCallback"], json!(have_callback), "diagram: {text}");
+            assert_eq!(
+                a["callbackEffective"],
+                json!(have_callback),
+                "diagram: {text}"
+            );
+            assert_eq!(
+                a["classes"],
+                json!(["clickable", "clickable"]),
+                "diagram: {text}"
+            );
+        }
+    }
+}
+
+#[test]
+fn parse_diagram_flowchart_security_level_loose_marks_callback_effective() {
+    let engine = Engine::new().with_site_config({
+        let mut cfg = MermaidConfig::empty_object();
+        cfg.set_value("securityLevel", json!("loose"));
+        cfg
+    });
+    let text = "graph TD\nA-->B\nclick A call myFn(1, \"two, too\", three)\nclick B call other()";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let nodes = res.model["nodes"].as_array().unwrap();
+    let a = nodes.iter().find(|n| n["id"] == json!("A")).unwrap();
+    assert_eq!(a["haveCallback"], json!(true));
+    assert_eq!(a["callback"]["function"], json!("myFn"));
+    assert_eq!(a["callback"]["args"], json!(["1", "two, too", "three"]));
+    assert_eq!(a["callbackEffective"], json!(true));
+
+    // Mermaid passes the vertex id when the callback has no arguments.
+    let b = nodes.iter().find(|n| n["id"] == json!("B")).unwrap();
+    assert_eq!(b["callback"]["function"], json!("other"));
+    assert_eq!(b["callback"]["args"], json!(["B"]));
+    assert_eq!(b["callbackEffective"], json!(true));
+
+    let render = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Flowchart(model) = render.model else {
+        panic!("expected flowchart render model");
+    };
+    let a = model.nodes.iter().find(|n| n.id == "A").unwrap();
+    assert!(a.callback_effective);
+    assert_eq!(
+        a.callback.as_ref().map(|c| c.args.clone()),
+        Some(vec![
+            "1".to_string(),
+            "two, too".to_string(),
+            "three".to_string()
+        ])
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_style_statement_supports_multiple_styles() {
     let engine = Engine::new();
@@ -2278,6 +3022,25 @@ fn parse_diagram_flowchart_style_statement_supports_multiple_styles() {
     );
 }
 
+#[test]
+fn parse_diagram_flowchart_class_def_and_style_keep_inner_spacing_like_mermaid() {
+    // FlowDB `addClass` / `addVertex` only trim each comma-separated declaration; the jison
+    // `styleComponent` rule keeps SPACE tokens, so `background: #bbb` stays as written.
+    let engine = Engine::new();
+    let text = "flowchart TD\nclassDef foo  background: #bbb , border:1px solid red\nA:::foo\nstyle A fill: #f9f ,stroke: #333";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.model["classDefs"]["foo"],
+        json!(["background: #bbb", "border:1px solid red"])
+    );
+    assert_eq!(
+        res.model["nodes"][0]["styles"],
+        json!(["fill: #f9f", "stroke: #333"])
+    );
+}
+
 #[test]
 fn parse_diagram_flowchart_classdef_supports_multiple_classes() {
     let engine = Engine::new();
@@ -2322,6 +3085,44 @@ graph TD
     assert_eq!(find("E")["classes"][0], json!("C2"));
 }
 
+#[test]
+fn parse_diagram_flowchart_inline_class_next_to_group_edges_matches_mermaid() {
+    let engine = Engine::new();
+    // `:::` only attaches to vertices; edges get classes through `class <edgeId>`. In a group,
+    // Mermaid gives the explicit edge id to the last-start/first-end pair (`B --> C`).
+    let text = r#"
+graph TD
+  classDef x stroke-dasharray:4
+  classDef hot stroke:red
+  A & B e1@--> C:::x & D
+  class e1 hot
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let classes = |kind: &str, id: &str| {
+        res.model[kind]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|n| n["id"] == json!(id))
+            .unwrap()["classes"]
+            .clone()
+    };
+    assert_eq!(classes("nodes", "A"), json!([]));
+    assert_eq!(classes("nodes", "B"), json!([]));
+    assert_eq!(classes("nodes", "C"), json!(["x"]));
+    assert_eq!(classes("nodes", "D"), json!([]));
+
+    let edges = res.model["edges"].as_array().unwrap();
+    let e1 = edges.iter().find(|e| e["id"] == json!("e1")).unwrap();
+    assert_eq!((&e1["from"], &e1["to"]), (&json!("B"), &json!("C")));
+    assert_eq!(e1["classes"], json!(["hot"]));
+    for id in ["L_A_C_0", "L_A_D_0", "L_B_D_0"] {
+        assert_eq!(classes("edges", id), json!([]), "edge: {id}");
+    }
+}
+
 #[test]
 fn parse_diagram_flowchart_keyword_flowchart() {
     let engine = Engine::new();
@@ -3029,3 +3830,252 @@ fn parse_flowchart_editor_facts_expect_target_after_pipe_edge_label() {
             && expected.span == SourceSpan::new(text.len(), text.len())
     }));
 }
+
+#[test]
+fn parse_diagram_flowchart_shape_and_layout_shape_stay_consistent_for_shape_data() {
+    let engine = Engine::new();
+    let text = r#"flowchart TD
+A[square]
+B(round)
+C((circle))
+D([stadium])
+E{{hex}}
+F@{ shape: rounded }
+G@{ shape: rect }
+H@{ shape: circle }
+I@{ shape: hex }
+J
+K[was square]
+K@{ shape: stadium }
+"#;
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let shapes = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| {
+            (
+                n["id"].as_str().unwrap(),
+                n["shape"].as_str(),
+                n["layoutShape"].as_str().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shapes,
+        [
+            ("A", Some("square"), "squareRect"),
+            ("B", Some("round"), "roundedRect"),
+            ("C", Some("circle"), "circle"),
+            ("D", Some("stadium"), "stadium"),
+            ("E", Some("hexagon"), "hexagon"),
+            ("F", Some("rounded"), "rounded"),
+            ("G", Some("rect"), "rect"),
+            ("H", Some("circle"), "circle"),
+            ("I", Some("hex"), "hex"),
+            ("J", None, "squareRect"),
+            ("K", Some("stadium"), "stadium"),
+        ]
+    );
+}
+
+#[test]
+fn parse_render_model_flowchart_resolves_node_styles_from_all_sources() {
+    let engine = Engine::new();
+    let text = r#"flowchart TD
+classDef default stroke:#000
+classDef warn fill:#f96,color:#fff
+classDef wide stroke-width:4px
+A:::warn --> B
+class A wide
+style A fill:#0f0
+"#;
+    let render = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::default())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Flowchart(model) = render.model else {
+        panic!("expected flowchart render model");
+    };
+
+    let a = model.nodes.iter().find(|n| n.id == "A").unwrap();
+    assert_eq!(a.classes, vec!["warn", "wide"]);
+    assert_eq!(a.styles, vec!["fill:#0f0"]);
+    assert_eq!(
+        a.resolved_styles,
+        vec![
+            "stroke:#000",
+            "fill:#f96",
+            "color:#fff",
+            "color:#fff",
+            "stroke-width:4px",
+            "fill:#0f0",
+        ]
+    );
+
+    let b = model.nodes.iter().find(|n| n.id == "B").unwrap();
+    assert_eq!(b.resolved_styles, vec!["stroke:#000"]);
+}
+
+#[test]
+fn parse_diagram_flowchart_accepts_unicode_letter_ids_and_rejects_emoji_ids() {
+    // Mermaid's `UNICODE_TEXT` id token covers letters (CJK, accented Latin), not symbols.
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram(
+        "graph TD\n节点-->B\nüber --> 日本語[Label]",
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    let ids = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["id"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![json!("节点"), json!("B"), json!("über"), json!("日本語")]
+    );
+    assert_eq!(res.model["edges"][0]["id"], json!("L_节点_B_0"));
+    assert_eq!(res.model["edges"][1]["id"], json!("L_über_日本語_0"));
+
+    let err =
+        block_on(engine.parse_diagram("graph TD\n🙂-->B", ParseOptions::default())).unwrap_err();
+    assert!(
+        err.to_string().contains("Unexpected character '🙂' at 9"),
+        "unexpected error: {err}"
+    );
+
+    let res = block_on(engine.parse_diagram("graph TD\nA[\"🙂\"]-->B", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.model["nodes"][0]["label"], json!("🙂"));
+}
+
+#[test]
+fn parse_diagram_flowchart_quoted_edge_label_keeps_newline_like_node_labels() {
+    let engine = Engine::new();
+    let text =
+        "flowchart TD\nA-- \"line1\nline2\" -->B\nC-->|\"line1\nline2\"|D\nN[\"line1\nline2\"]\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let node = res.model["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == json!("N"))
+        .unwrap();
+    assert_eq!(node["label"], json!("line1\nline2"));
+    assert_eq!(node["labelType"], json!("string"));
+
+    let edges = res.model["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 2);
+    for edge in edges {
+        assert_eq!(edge["label"], node["label"]);
+        assert_eq!(edge["labelType"], node["labelType"]);
+    }
+}
+
+#[test]
+fn flowchart_class_def_text_styles_match_flowdb_add_class() {
+    let engine = Engine::new();
+    let text =
+        "flowchart TD\nclassDef warn fill:#f00,color:pink,font-weight:bold,stroke:#000\nA:::warn\n";
+    let RenderSemanticModel::Flowchart(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected flowchart render model");
+    };
+
+    assert_eq!(
+        model.class_defs["warn"],
+        vec!["fill:#f00", "color:pink", "font-weight:bold", "stroke:#000"]
+    );
+    // Only `color` declarations are mirrored into text styles, as in Mermaid's DBs.
+    assert_eq!(model.class_def_text_styles("warn"), vec!["color:pink"]);
+    assert!(model.class_def_text_styles("missing").is_empty());
+}
+
+#[test]
+fn parse_diagram_flowchart_top_level_direction_statement_does_not_override_header() {
+    // Mermaid's flowDb only applies `direction` statements inside subgraphs; at the top level
+    // the statement is accepted and dropped, so the header direction stays in effect.
+    let engine = Engine::new();
+    for text in [
+        "graph TD\nA-->B\ndirection LR\nC-->D",
+        "graph TD;direction LR;A-->B;",
+        "flowchart RL\ndirection BT\nA-->B",
+    ] {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        let expected = if text.starts_with("flowchart RL") {
+            "RL"
+        } else {
+            "TB"
+        };
+        assert_eq!(res.model["direction"], json!(expected), "{text}");
+        assert_eq!(res.model["subgraphs"], json!([]), "{text}");
+        assert!(
+            res.model["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .all(|n| n["id"] != json!("direction")),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn parse_diagram_flowchart_strips_trailing_comments_but_keeps_percent_pairs_in_labels() {
+    let engine = Engine::new();
+    let parse = |text: &str| {
+        block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap()
+            .model
+    };
+
+    // A trailing `%%` comment ends the statement without eating the edge or the next line.
+    for text in [
+        "flowchart TD\nA-->B %% this is B\nB-->C\n",
+        "flowchart TD\nA-->B;%% this is B\nB-->C\n",
+        "flowchart TD\n%% leading comment line\nA-->B\nB-->C %% last\n",
+    ] {
+        let model = parse(text);
+        let edges: Vec<_> = model["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["from"].clone(), e["to"].clone()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![(json!("A"), json!("B")), (json!("B"), json!("C"))],
+            "{text}"
+        );
+        let ids: Vec<_> = model["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].clone())
+            .collect();
+        assert_eq!(ids, vec![json!("A"), json!("B"), json!("C")], "{text}");
+    }
+
+    // `%%` inside quoted node and edge labels is label text, not a comment.
+    let model = parse("flowchart TD\nA[\"50%% done\"] -->|\"x %% y\"| B{\"%% q\"}\n");
+    assert_eq!(model["nodes"][0]["label"], json!("50%% done"));
+    assert_eq!(model["nodes"][1]["label"], json!("%% q"));
+    assert_eq!(model["edges"][0]["label"], json!("x %% y"));
+}
@@ -75,6 +75,33 @@ fn parse_indented_headers_across_common_diagrams() {
     }
 }
 
+#[test]
+fn parse_metadata_warnings_default_to_empty() {
+    let engine = Engine::new();
+    let res =
+        block_on(engine.parse_metadata("%%{wrap}%%\ngraph TD;A-->B;", ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+    assert!(res.warnings.is_empty());
+}
+
+#[test]
+fn parse_metadata_warns_about_ignored_directive_types() {
+    let engine = Engine::new();
+    let text = "%%{wrap}%%\n%%{themeCss}%%\nsequenceDiagram\nAlice->>Bob: hi";
+
+    let res = block_on(engine.parse_metadata(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.diagram_type, "sequence");
+    assert_eq!(res.warnings, ["ignored unsupported directive `themeCss`"]);
+
+    let parsed = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(parsed.meta.warnings, res.warnings);
+}
+
 #[test]
 #[cfg(feature = "full-config")]
 fn parse_merges_frontmatter_and_directive_config() {
@@ -219,6 +246,60 @@ graph TD;A-->B;"##;
     );
 }
 
+#[test]
+fn explicit_frontmatter_theme_variables_override_theme_palette() {
+    let engine = Engine::new().with_site_config(MermaidConfig::from_value(json!({
+        "secure": ["secure", "securityLevel", "startOnLoad", "maxTextSize", "suppressErrorRendering", "maxEdges"]
+    })));
+    let text = r##"---
+config:
+  theme: forest
+  themeVariables:
+    primaryColor: "#f00"
+---
+graph TD;A-->B;"##;
+
+    let res = block_on(engine.parse_metadata(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        res.effective_config.get_str("themeVariables.primaryColor"),
+        Some("#f00")
+    );
+    assert_eq!(
+        res.effective_config.get_str("themeVariables.mainBkg"),
+        Some("#f00")
+    );
+    // Keys the user did not set still come from the forest palette.
+    assert_eq!(
+        res.effective_config
+            .get_str("themeVariables.secondaryColor"),
+        Some("#cdffb2")
+    );
+}
+
+#[test]
+fn site_theme_variables_override_theme_palette() {
+    let engine = Engine::new().with_site_config(MermaidConfig::from_value(json!({
+        "theme": "forest",
+        "themeVariables": { "primaryColor": "#f00" }
+    })));
+
+    let res = block_on(engine.parse_metadata("graph TD;A-->B;", ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        res.effective_config.get_str("themeVariables.primaryColor"),
+        Some("#f00")
+    );
+    assert_eq!(
+        res.effective_config.get_str("themeVariables.mainBkg"),
+        Some("#f00")
+    );
+}
+
 #[test]
 #[cfg(feature = "full-config")]
 fn parse_maps_top_level_frontmatter_diagram_config() {
@@ -487,6 +568,35 @@ Alice->Bob: Hi
     assert_eq!(res.config.as_value(), &json!({ "logLevel": 0 }));
 }
 
+#[test]
+fn parse_init_directive_accepts_unquoted_and_mixed_quote_forms() {
+    let engine = Engine::new();
+    for text in [
+        "%%{init: {theme: base}}%%\ngraph TD;A-->B;",
+        "%%{init: {'theme': \"base\"}}%%\ngraph TD;A-->B;",
+        "%%{init: {\"theme\": 'base'}}%%\ngraph TD;A-->B;",
+        "%%{init: {theme: 'base', \"themeVariables\": {primaryColor: '#ff0000'}}}%%\ngraph TD;A-->B;",
+    ] {
+        let res = block_on(engine.parse_metadata(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.config.get_str("theme"), Some("base"), "{text}");
+    }
+
+    let text = "%%{init: {theme: base, themeVariables: {primaryColor: '#ff0000'}, logLevel: 1}}%%\ngraph TD;A-->B;";
+    let res = block_on(engine.parse_metadata(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        res.config.as_value(),
+        &json!({
+            "theme": "base",
+            "themeVariables": { "primaryColor": "#ff0000" },
+            "logLevel": 1
+        })
+    );
+}
+
 #[test]
 fn parse_init_font_family_mirrors_retained_config_but_default_secure_filters_effective_config() {
     let engine = Engine::new();
@@ -1114,6 +1224,7 @@ fn parse_can_suppress_unknown_diagram_errors() {
         "this is not a mermaid diagram definition",
         ParseOptions {
             suppress_errors: true,
+            ..Default::default()
         },
     ))
     .unwrap();
@@ -2641,3 +2752,124 @@ accDescr: <script>alert(1)</script><b>d</b>
         other => panic!("treemap render parse should return typed model, got {other:?}"),
     }
 }
+
+#[test]
+fn max_input_size_rejects_oversized_input_before_preprocessing() {
+    let engine = Engine::new();
+    let text = "flowchart TD\nA-->B\n";
+    let options = ParseOptions::lenient().with_max_input_size(8);
+
+    let err = engine.parse_metadata_sync(text, options).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::InputTooLarge {
+            size,
+            limit: 8
+        } if size == text.len()
+    ));
+
+    // The guard runs before front-matter/directive handling and ignores `suppress_errors`.
+    let malformed = "---\nconfig: [\n---\nflowchart TD\nA-->B\n";
+    let err = engine
+        .parse_diagram_sync(malformed, ParseOptions::lenient().with_max_input_size(4))
+        .unwrap_err();
+    assert!(matches!(err, Error::InputTooLarge { limit: 4, .. }));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Input is {} bytes, exceeding max_input_size 4",
+            malformed.len()
+        )
+    );
+}
+
+#[test]
+fn max_input_size_allows_input_at_the_limit() {
+    let engine = Engine::new();
+    let text = "flowchart TD\nA-->B\n";
+
+    let res = engine
+        .parse_diagram_sync(text, ParseOptions::strict().with_max_input_size(text.len()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.meta.diagram_type, "flowchart-v2");
+    assert_eq!(ParseOptions::default().max_input_size, None);
+}
+
+#[test]
+fn engine_is_shareable_across_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Engine>();
+
+    let engine = std::sync::Arc::new(Engine::new());
+    let inputs = [
+        ("graph TD\nA-->B", "flowchart-v2"),
+        ("sequenceDiagram\nAlice->>Bob: Hi", "sequence"),
+        ("pie\n\"a\": 1", "pie"),
+        ("stateDiagram-v2\n[*] --> S1", "stateDiagram"),
+    ];
+    let handles = inputs
+        .into_iter()
+        .map(|(text, diagram_type)| {
+            let engine = std::sync::Arc::clone(&engine);
+            std::thread::spawn(move || {
+                for _ in 0..8 {
+                    let parsed = engine
+                        .parse_diagram_sync(text, ParseOptions::strict())
+                        .unwrap()
+                        .unwrap();
+                    assert_eq!(parsed.meta.diagram_type, diagram_type);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn acc_descr_block_and_single_line_forms_agree_across_diagram_types() {
+    let engine = Engine::new();
+    let headers = [
+        "pie",
+        "sequenceDiagram",
+        "classDiagram",
+        "erDiagram",
+        "stateDiagram-v2",
+        "journey",
+        "gantt",
+        "architecture-beta",
+    ];
+    let cases = [
+        ("accDescr {\n  a\n\n    b\n}\n", "a\nb"),
+        ("accDescr {   a\nb   }\n", "a\nb"),
+        ("accDescr { one line }\n", "one line"),
+        ("accDescr:   one line  \n", "one line"),
+    ];
+
+    for header in headers {
+        for (body, expected) in cases {
+            let text = format!("{header}\n{body}");
+            let res = block_on(engine.parse_diagram(&text, ParseOptions::default()))
+                .unwrap_or_else(|e| panic!("{header} {body:?}: {e}"))
+                .unwrap();
+            assert_eq!(
+                res.model["accDescr"],
+                json!(expected),
+                "{header} with {body:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn pie_single_line_acc_descr_block_does_not_swallow_sections() {
+    let engine = Engine::new();
+    let text = "pie\naccDescr { slices }\n\"a\": 1\n\"b\": 2\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.model["accDescr"], json!("slices"));
+    assert_eq!(res.model["sections"].as_array().unwrap().len(), 2);
+}
@@ -35,6 +35,34 @@ Bob-->Alice: I am good thanks!"#;
     assert_eq!(msgs[2]["type"], json!(6));
 }
 
+#[test]
+fn parse_diagram_sequence_note_over_undeclared_actor_creates_it() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+participant A
+Note over A,Ghost: spans both"#;
+
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(res.model["actorOrder"], json!(["A", "Ghost"]));
+    let ghost = &res.model["actors"]["Ghost"];
+    assert_eq!(ghost["name"], json!("Ghost"));
+    assert_eq!(ghost["description"], json!("Ghost"));
+    assert_eq!(ghost["type"], json!("participant"));
+
+    let notes = res.model["notes"].as_array().unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0]["actor"], json!(["A", "Ghost"]));
+
+    let msgs = res.model["messages"].as_array().unwrap();
+    assert_eq!(msgs.len(), 1);
+    assert_eq!(msgs[0]["from"], json!("A"));
+    assert_eq!(msgs[0]["to"], json!("Ghost"));
+    assert_eq!(msgs[0]["placement"], json!(2));
+}
+
 #[test]
 fn parse_sequence_editor_facts_preserve_actor_and_box_spans() {
     let engine = Engine::new();
@@ -611,6 +639,56 @@ link a: Tests @ https://tests.contoso.com/?svc=alice@contoso.com
     );
 }
 
+#[test]
+fn parse_diagram_sequence_links_warns_on_invalid_json() {
+    let engine = Engine::new();
+    for (body, kind, near) in [
+        (
+            r#"links a: { "Repo": "https://repo.contoso.com/", "Dashboard": "https://dashboard.contoso.com/", }"#,
+            "links",
+            "toso.com/\", }",
+        ),
+        (
+            r#"links a: { Repo: "https://repo.contoso.com/" }"#,
+            "links",
+            "{ Repo: \"https",
+        ),
+        (
+            r#"properties a: { "class": "internal", }"#,
+            "properties",
+            "\"internal\", }",
+        ),
+    ] {
+        let text = format!("sequenceDiagram\nparticipant a as Alice\n{body}\n");
+
+        let res = block_on(engine.parse_diagram(&text, ParseOptions::default()))
+            .expect("malformed JSON is ignored like Mermaid does")
+            .unwrap();
+        assert_eq!(res.model["actors"]["a"][kind], json!({}));
+
+        let facts = res.model["warningFacts"].as_array().unwrap();
+        assert_eq!(facts.len(), 1, "{facts:?}");
+        assert_eq!(
+            facts[0]["ruleId"],
+            json!(SEQUENCE_INVALID_ACTOR_JSON_WARNING_RULE_ID)
+        );
+        let msg = facts[0]["message"].as_str().unwrap();
+        assert!(
+            msg.starts_with(&format!("Invalid JSON in {kind} for actor 'a':")),
+            "unexpected warning: {msg}"
+        );
+        assert!(
+            msg.ends_with(&format!("(near `{near}`)")),
+            "unexpected warning: {msg}"
+        );
+        assert_eq!(res.model["warnings"], json!([msg]));
+
+        let start = facts[0]["span"]["start"].as_u64().unwrap() as usize;
+        let end = facts[0]["span"]["end"].as_u64().unwrap() as usize;
+        assert_eq!(text[start..end].trim_end(), body);
+    }
+}
+
 #[test]
 fn parse_diagram_sequence_allows_keyword_like_actor_ids() {
     let engine = Engine::new();
@@ -672,6 +750,35 @@ properties b: {"class": "external-service-actor", "icon": "@computer"}
     assert_eq!(actors["c"]["properties"].get("class"), None);
 }
 
+#[test]
+fn parse_diagram_sequence_actor_fill_property_is_independent_of_box_fill() {
+    // Mermaid has no dedicated per-participant color statement; `properties` is passed through
+    // verbatim, so a `fill` key lives on the actor and never leaks into box membership or color.
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+box green Group 1
+participant a as Alice
+participant b as Bob
+end
+participant c as Charlie
+properties b: {"fill": "pink"}
+properties c: {"fill": "cyan"}
+"#;
+
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let actors = res.model["actors"].as_object().unwrap();
+    assert_eq!(actors["a"]["properties"].get("fill"), None);
+    assert_eq!(actors["b"]["properties"]["fill"], json!("pink"));
+    assert_eq!(actors["c"]["properties"]["fill"], json!("cyan"));
+
+    let boxes = res.model["boxes"].as_array().unwrap();
+    assert_eq!(boxes.len(), 1);
+    assert_eq!(boxes[0]["actorKeys"], json!(["a", "b"]));
+    assert_eq!(boxes[0]["fill"], json!("green"));
+}
+
 #[test]
 fn parse_diagram_sequence_box_color_and_membership() {
     let engine = Engine::new();
@@ -717,6 +824,40 @@ end
     assert_eq!(boxes[0]["fill"], json!("transparent"));
 }
 
+#[test]
+fn sequence_actors_link_back_to_their_box() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+box green Group 1
+participant a as Alice
+participant b as Bob
+end
+participant c as Carol
+box aqua Group 2
+participant d as Dave
+end
+a ->> d: hi
+"#;
+
+    let RenderSemanticModel::Sequence(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected sequence render model");
+    };
+    for (index, sequence_box) in model.boxes.iter().enumerate() {
+        for key in &sequence_box.actor_keys {
+            assert_eq!(model.actors[key].box_index, Some(index), "actor {key}");
+        }
+    }
+    assert_eq!(model.actors["a"].box_index, Some(0));
+    assert_eq!(model.actors["d"].box_index, Some(1));
+    assert_eq!(model.boxes[1].fill, "aqua");
+    assert_eq!(model.actors["c"].box_index, None);
+}
+
 #[test]
 fn parse_diagram_sequence_box_without_description_has_falsy_name() {
     let engine = Engine::new();
@@ -785,6 +926,119 @@ a ->> d: Hello Donald?
     assert_eq!(created["d"], json!(3));
 }
 
+#[test]
+fn parse_diagram_sequence_create_index_counts_control_messages() {
+    // The recorded index points at the creating message inside `messages`, which also holds the
+    // block control entries (alt start, else, end).
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+a ->> b: Hello
+alt ok
+a ->> b: Yes
+else not ok
+a ->> b: No
+end
+create participant c
+b ->> c: Hello c!
+destroy c
+b ->> c: Bye c!
+"#;
+
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let created = res.model["createdActors"].as_object().unwrap();
+    let destroyed = res.model["destroyedActors"].as_object().unwrap();
+    let messages = res.model["messages"].as_array().unwrap();
+
+    assert_eq!(created["c"], json!(6));
+    assert_eq!(messages[6]["to"], json!("c"));
+    assert_eq!(messages[6]["message"], json!("Hello c!"));
+    assert_eq!(destroyed["c"], json!(7));
+    assert_eq!(messages[7]["message"], json!("Bye c!"));
+}
+
+#[test]
+fn parse_diagram_sequence_create_destroy_indices_are_stable_across_parses() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+a ->> b: Hello
+create participant c
+b ->> c: Hello c!
+destroy c
+c ->> b: Bye!
+create actor d
+b ->> d: Hi d
+"#;
+    let other =
+        "sequenceDiagram\nx ->> y: one\ny ->> x: two\ncreate participant z\nx ->> z: three\n";
+
+    let indices = |text: &str| {
+        let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+            .unwrap()
+            .unwrap();
+        (
+            res.model["createdActors"].clone(),
+            res.model["destroyedActors"].clone(),
+        )
+    };
+
+    let first = indices(text);
+    let _ = indices(other);
+    let second = indices(text);
+
+    assert_eq!(first, (json!({"c": 1, "d": 3}), json!({"c": 2})));
+    assert_eq!(first, second);
+
+    let RenderSemanticModel::Sequence(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected sequence render model");
+    };
+    assert_eq!(model.created_actors.get("c"), Some(&1));
+    assert_eq!(model.created_actors.get("d"), Some(&3));
+    assert_eq!(model.destroyed_actors.get("c"), Some(&2));
+}
+
+#[test]
+fn sequence_lifeline_extents_bracket_messages_touching_each_actor() {
+    use crate::diagrams::sequence::SequenceLifelineExtent;
+
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+a ->> b: one
+a ->> b: two
+create participant c
+b ->> c: three
+Note over c: four
+destroy c
+c ->> b: five
+a ->> b: six
+"#;
+    let RenderSemanticModel::Sequence(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected sequence render model");
+    };
+
+    let extents = model.lifeline_extents();
+    let extent = |first_seen, last_seen| SequenceLifelineExtent {
+        first_seen,
+        last_seen,
+    };
+    assert_eq!(extents.get("a"), Some(&extent(0, 5)));
+    assert_eq!(extents.get("b"), Some(&extent(0, 5)));
+    assert_eq!(extents.get("c"), Some(&extent(2, 4)));
+    assert_eq!(model.messages[2].message_text(), "three");
+    assert_eq!(model.messages[4].message_text(), "five");
+}
+
 #[test]
 fn parse_diagram_sequence_destroy_participant_marks_destroyed_actor_index() {
     let engine = Engine::new();
@@ -1078,6 +1332,119 @@ end"#;
     assert_eq!(messages[4]["type"], json!(21));
 }
 
+#[test]
+fn sequence_render_model_par_blocks_count_and_regions() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+par Alice to Bob
+Alice ->> Bob: one
+and Alice to Carol
+Alice ->> Carol: two
+and Alice to Dana
+Alice ->> Dana: three
+end"#;
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+
+    let blocks = model.par_blocks();
+    assert_eq!(
+        blocks,
+        [crate::diagrams::sequence::SequenceParBlock {
+            start: 0,
+            separators: vec![2, 4],
+            end: Some(6),
+        }]
+    );
+    assert_eq!(blocks[0].regions(), 3);
+}
+
+#[test]
+fn sequence_render_model_par_blocks_assign_separators_to_innermost_block() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+par outer
+Alice ->> Bob: one
+and outer second
+par inner
+Bob ->> Carol: two
+and inner second
+Bob ->> Dana: three
+end
+end"#;
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+
+    let blocks = model.par_blocks();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!((blocks[0].start, blocks[0].end), (0, Some(8)));
+    assert_eq!(blocks[0].separators, [2]);
+    assert_eq!((blocks[1].start, blocks[1].end), (3, Some(7)));
+    assert_eq!(blocks[1].separators, [5]);
+    assert_eq!(
+        blocks.iter().map(|b| b.regions()).collect::<Vec<_>>(),
+        [2, 2]
+    );
+}
+
+#[test]
+fn sequence_render_model_block_control_messages_carry_nesting_depth() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+alt ok
+Alice ->> Bob: hi
+loop retry
+Bob ->> Alice: again
+end
+else failed
+Alice ->> Bob: bye
+end
+opt later
+Alice ->> Bob: maybe
+end"#;
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+
+    let depths = model
+        .messages
+        .iter()
+        .map(|m| (m.message_text(), m.depth))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        depths,
+        [
+            ("ok", Some(0)),
+            ("hi", None),
+            ("retry", Some(1)),
+            ("again", None),
+            ("", Some(1)),
+            ("failed", Some(0)),
+            ("bye", None),
+            ("", Some(0)),
+            ("later", Some(0)),
+            ("maybe", None),
+            ("", Some(0)),
+        ]
+    );
+}
+
 #[test]
 fn parse_diagram_sequence_special_characters_in_loop_opt_alt_par() {
     let engine = Engine::new();
@@ -1207,3 +1574,199 @@ end"#,
     assert_eq!(messages[3]["message"], json!(""));
     assert_eq!(messages[4]["message"], json!("I am good thanks!"));
 }
+
+#[test]
+fn parse_diagram_with_spans_maps_sequence_messages_to_statements() {
+    let engine = Engine::new();
+    let text = "---\ntitle: Spans\n---\nsequenceDiagram\n%% greeting\nAlice->>Bob: Hello\nloop Every minute\n  Bob-->>+Alice: Hi back\nend\n";
+    let parsed = engine
+        .parse_diagram_sync(text, ParseOptions::strict().with_spans())
+        .unwrap()
+        .unwrap();
+    let spans = parsed.spans.expect("spans were requested");
+    let messages = parsed.model["messages"].as_array().unwrap();
+    assert_eq!(spans.messages.len(), messages.len());
+
+    let source_at = |index: usize| &text[spans.messages[&index].clone()];
+    assert_eq!(source_at(0), "Alice->>Bob: Hello");
+    assert_eq!(source_at(1), "loop Every minute");
+    // The message and the activation it implies share one statement.
+    assert_eq!(source_at(2), "Bob-->>+Alice: Hi back");
+    assert_eq!(source_at(3), "Bob-->>+Alice: Hi back");
+    assert_eq!(source_at(4), "end");
+    assert!(spans.nodes.is_empty() && spans.edges.is_empty());
+
+    let without = engine
+        .parse_diagram_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    assert!(without.spans.is_none());
+    assert_eq!(without.model, parsed.model);
+}
+
+#[test]
+fn sequence_render_model_carries_resolved_sequence_config() {
+    let engine = Engine::new();
+
+    let defaults = engine
+        .parse_diagram_for_render_model_sync("sequenceDiagram\nA->>B: hi\n", ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &defaults.model else {
+        panic!("expected typed sequence model, got {:?}", defaults.model);
+    };
+    assert_eq!(
+        model.config,
+        crate::diagrams::sequence::SequenceResolvedConfig {
+            mirror_actors: true,
+            wrap: false,
+            show_sequence_numbers: false,
+        }
+    );
+
+    let text = "%%{init: {\"wrap\": true, \"sequence\": {\"mirrorActors\": false, \"showSequenceNumbers\": true}}}%%\nsequenceDiagram\nA->>B: hi\n";
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+    assert!(!model.config.mirror_actors);
+    assert!(model.config.wrap);
+    assert!(model.config.show_sequence_numbers);
+}
+
+#[test]
+fn sequence_numbers_follow_show_sequence_numbers_directive_and_autonumber() {
+    let engine = Engine::new();
+    let numbers = |text: &str| {
+        let parsed = engine
+            .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+            .unwrap()
+            .unwrap();
+        let RenderSemanticModel::Sequence(model) = parsed.model else {
+            panic!("expected typed sequence model, got {:?}", parsed.model);
+        };
+        (
+            model.config,
+            model.messages.iter().all(|m| m.wrap),
+            model.sequence_numbers(),
+        )
+    };
+
+    let (config, _, plain) = numbers("sequenceDiagram\nA->>B: one\nB-->>A: two\n");
+    assert!(!config.show_sequence_numbers);
+    assert_eq!(plain, vec![None, None]);
+
+    let directive = "%%{init: {\"sequence\": {\"wrap\": true, \"showSequenceNumbers\": true}}}%%\n";
+    let (config, all_wrapped, numbered) = numbers(&format!(
+        "{directive}sequenceDiagram\nA->>B: one\nNote over A: note\nB-->>A: two\nA->>B: three\n"
+    ));
+    assert!(config.show_sequence_numbers);
+    assert!(config.wrap);
+    assert!(all_wrapped);
+    assert_eq!(numbered, vec![Some(1.0), None, Some(2.0), Some(3.0)]);
+
+    // `autonumber` still sets start / step; `autonumber off` cannot hide numbers the config
+    // shows globally.
+    let (_, _, restarted) = numbers(&format!(
+        "{directive}sequenceDiagram\nA->>B: one\nautonumber 10 5\nA->>B: two\nautonumber off\nA->>B: three\n"
+    ));
+    assert_eq!(
+        restarted,
+        vec![Some(1.0), None, Some(10.0), None, Some(15.0)]
+    );
+
+    let (_, _, toggled) = numbers(
+        "sequenceDiagram\nA->>B: hidden\nautonumber\nA->>B: shown\nautonumber off\nA->>B: hidden\n",
+    );
+    assert_eq!(toggled, vec![None, None, Some(2.0), None, None]);
+}
+
+#[test]
+fn sequence_render_model_message_arrow_names_each_signal_token() {
+    use crate::diagrams::sequence::SequenceArrow;
+
+    let engine = Engine::new();
+    let text = "sequenceDiagram\nA->B: open\nA-->B: dotted open\nA->>B: solid\nA-->>B: dotted\nA-xB: cross\nA--xB: dotted cross\nA-)B: async\nA--)B: dotted async\nA<<->>B: both\nA<<-->>B: dotted both\nNote over A: not a signal\n";
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+
+    let arrows = model.messages.iter().map(|m| m.arrow()).collect::<Vec<_>>();
+    assert_eq!(
+        arrows,
+        [
+            Some(SequenceArrow::SolidOpen),
+            Some(SequenceArrow::DottedOpen),
+            Some(SequenceArrow::Solid),
+            Some(SequenceArrow::Dotted),
+            Some(SequenceArrow::SolidCross),
+            Some(SequenceArrow::DottedCross),
+            Some(SequenceArrow::SolidPoint),
+            Some(SequenceArrow::DottedPoint),
+            Some(SequenceArrow::BidirectionalSolid),
+            Some(SequenceArrow::BidirectionalDotted),
+            None,
+        ]
+    );
+    for (message, arrow) in model.messages.iter().zip(arrows.iter().flatten()) {
+        assert_eq!(arrow.line_type(), message.message_type);
+    }
+    assert_eq!(SequenceArrow::SolidCross.as_str(), "solidCross");
+    assert!(SequenceArrow::DottedPoint.is_dotted());
+    assert!(!SequenceArrow::Solid.is_dotted());
+}
+
+#[test]
+fn sequence_render_model_note_lines_normalize_br_variants_and_newline_escapes() {
+    let engine = Engine::new();
+    let text = r#"sequenceDiagram
+Note right of Bob: line1<br>line2
+Note right of Bob: line1<br/>line2
+Note right of Bob: line1<BR />line2
+Note right of Bob: line1\nline2
+"#;
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::Sequence(model) = &parsed.model else {
+        panic!("expected typed sequence model, got {:?}", parsed.model);
+    };
+
+    assert_eq!(model.notes.len(), 4);
+    assert_eq!(model.notes[0].message, "line1<br>line2");
+    for note in &model.notes {
+        assert_eq!(note.lines(), vec!["line1", "line2"], "{}", note.message);
+    }
+}
+
+#[test]
+fn sequence_message_wrapped_lines_only_when_wrap_is_enabled() {
+    let engine = Engine::new();
+    let text = "sequenceDiagram\nA->>B:wrap: the quick brown fox\nA->>B: the quick brown fox\n";
+    let RenderSemanticModel::Sequence(model) = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap()
+        .model
+    else {
+        panic!("expected sequence render model");
+    };
+
+    assert!(model.messages[0].wrap);
+    assert_eq!(
+        model.messages[0].wrapped_lines(10),
+        Some(vec!["the quick".to_string(), "brown fox".to_string()])
+    );
+    assert_eq!(model.messages[0].wrapped_lines(0), None);
+    assert!(!model.messages[1].wrap);
+    assert_eq!(model.messages[1].wrapped_lines(10), None);
+}
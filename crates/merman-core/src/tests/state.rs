@@ -143,6 +143,43 @@ state Explicit {
     assert!(node("B").get("explicitDir").is_none());
 }
 
+#[test]
+fn parse_diagram_state_v2_composite_direction_with_transitions_does_not_leak_into_nested_states() {
+    // Mermaid's `getDir` falls back to `TB` for every composite without its own `direction`,
+    // so nested composites do not inherit the parent's direction.
+    let res = block_on(Engine::new().parse_diagram(
+        r#"stateDiagram-v2
+direction RL
+state Big {
+  direction LR
+  a --> b
+  state Inner {
+    c --> d
+  }
+}
+"#,
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(res.model["direction"], json!("RL"));
+    let nodes = res.model["nodes"].as_array().expect("state render nodes");
+    let node = |id: &str| {
+        nodes
+            .iter()
+            .find(|node| node["id"] == id)
+            .unwrap_or_else(|| panic!("missing state node {id}"))
+    };
+
+    assert_eq!(node("Big")["dir"], json!("LR"));
+    assert_eq!(node("Big")["explicitDir"], json!(true));
+    assert_eq!(node("Inner")["dir"], json!("TB"));
+    assert_eq!(node("Inner")["explicitDir"], json!(false));
+    assert_eq!(node("a")["parentId"], json!("Big"));
+    assert_eq!(node("c")["parentId"], json!("Inner"));
+}
+
 #[test]
 fn parse_state_render_model_preserves_alias_trailing_description() {
     let input = r#"stateDiagram-v2
@@ -328,6 +365,29 @@ class a,b,c, d, e exampleStyleClass"#,
     }
 }
 
+#[test]
+fn parse_diagram_state_v2_class_def_and_style_keep_inner_spacing_like_mermaid() {
+    // StateDB `addStyleClass` trims each declaration but leaves the space after `:` alone.
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram(
+        r#"stateDiagram-v2
+A
+classDef foo background: #bbb , border:1px solid red
+style A fill: #f9f ,stroke: #333"#,
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        res.model["styleClasses"]["foo"]["styles"],
+        json!(["background: #bbb", "border:1px solid red"])
+    );
+    assert_eq!(
+        res.model["states"]["A"]["styles"],
+        json!(["fill: #f9f", "stroke: #333"])
+    );
+}
+
 #[test]
 fn parse_diagram_state_v2_style_statement_sets_node_styles_and_ignores_comments() {
     let engine = Engine::new();
@@ -455,6 +515,38 @@ note "This is a floating note" as N1"#,
     assert!(res.model["states"].get("N1").is_none());
 }
 
+#[test]
+fn parse_diagram_state_v2_note_after_transition_binds_to_named_state() {
+    let engine = Engine::new();
+
+    // Notes never attach to transitions: `note <position> of X` always binds to state `X`, even
+    // when it directly follows a transition that names another state first.
+    let res = block_on(engine.parse_diagram(
+        r#"stateDiagram-v2
+A --> B
+note right of B : about B"#,
+        ParseOptions::default(),
+    ))
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(res.model["states"]["B"]["note"]["text"], json!("about B"));
+    assert_eq!(
+        res.model["states"]["B"]["note"]["position"],
+        json!("right of")
+    );
+    assert!(res.model["states"]["A"]["note"].is_null());
+
+    let note_edge = res.model["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["classes"] == json!("transition note-edge"))
+        .unwrap();
+    assert_eq!(note_edge["start"], json!("B"));
+    assert!(note_edge["end"].as_str().unwrap().starts_with("B----note"));
+}
+
 #[test]
 fn parse_diagram_state_v2_getdata_edges_and_note_edges() {
     let engine = Engine::new();
@@ -963,3 +1055,58 @@ fn assert_expected_syntax_covers(
         "missing {label}"
     );
 }
+
+#[test]
+fn parse_diagram_state_v2_classes_apply_to_composite_and_scoped_inner_states() {
+    let text = r#"stateDiagram-v2
+classDef outer fill:#f00
+classDef inner fill:#0f0
+state Big:::outer {
+  state Mid {
+    leaf --> other
+  }
+}
+class Big.Mid.leaf inner
+class Big.missing inner"#;
+    let engine = Engine::new();
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+    let states = res.model["states"].as_object().unwrap();
+    assert_eq!(states["Big"]["classes"], json!(["outer"]));
+    assert_eq!(states["leaf"]["classes"], json!(["inner"]));
+    assert!(!states.contains_key("Big.Mid.leaf"));
+    assert!(!states.contains_key("other"));
+    // A path that does not name a nested state is kept verbatim.
+    assert_eq!(states["Big.missing"]["classes"], json!(["inner"]));
+
+    let parsed = engine
+        .parse_diagram_for_render_model_sync(text, ParseOptions::strict())
+        .unwrap()
+        .unwrap();
+    let RenderSemanticModel::State(model) = parsed.model else {
+        panic!("expected typed state render model");
+    };
+    let node = |id: &str| model.nodes.iter().find(|n| n.id == id).unwrap();
+    assert!(node("Big").css_classes.starts_with("outer "));
+    assert!(node("leaf").css_classes.starts_with("inner "));
+    assert_eq!(node("leaf").parent_id.as_deref(), Some("Mid"));
+    assert!(!node("other").css_classes.contains("inner"));
+}
+
+#[test]
+fn parse_diagram_state_class_def_splits_color_into_text_styles() {
+    let engine = Engine::new();
+    let text = "stateDiagram-v2\nclassDef warn fill:#f00,color:pink,font-weight:bold\nA:::warn\n";
+    let res = block_on(engine.parse_diagram(text, ParseOptions::default()))
+        .unwrap()
+        .unwrap();
+
+    let warn = &res.model["styleClasses"]["warn"];
+    assert_eq!(
+        warn["styles"],
+        json!(["fill:#f00", "color:pink", "font-weight:bold"])
+    );
+    // StateDB renames `color` to `fill` for the text style.
+    assert_eq!(warn["textStyles"], json!(["fill:pink"]));
+}
@@ -236,6 +236,7 @@ fn fixtures_match_golden_snapshots() {
             &text,
             ParseOptions {
                 suppress_errors: true,
+                ..Default::default()
             },
         ))
         .unwrap_or_else(|e| panic!("parse failed for {}: {e}", mmd_path.display()))
@@ -1186,9 +1186,13 @@ mod tests {
             asset_height: None,
             classes: Vec::new(),
             styles: Vec::new(),
+            resolved_styles: Vec::new(),
             link: None,
             link_target: None,
+            link_target_effective: None,
             have_callback: false,
+            callback: None,
+            callback_effective: false,
         }
     }
 
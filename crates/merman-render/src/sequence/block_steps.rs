@@ -367,6 +367,7 @@ mod tests {
             activate: false,
             placement: (message_type == 2).then_some(1),
             central_connection: 0,
+            depth: None,
         }
     }
 
@@ -355,6 +355,7 @@ mod tests {
             activate: false,
             placement: Some(1),
             central_connection: 0,
+            depth: None,
         };
         let actor_index = HashMap::from([("A", 0), ("B", 1)]);
         let note_style = TextStyle {
@@ -92,9 +92,13 @@ pub(in crate::svg::parity::flowchart) fn prepare_flowchart_render_inputs<'a>(
             asset_height: None,
             classes: Vec::new(),
             styles: Vec::new(),
+            resolved_styles: Vec::new(),
             have_callback: false,
+            callback: None,
+            callback_effective: false,
             link: None,
             link_target: None,
+            link_target_effective: None,
         });
     }
 
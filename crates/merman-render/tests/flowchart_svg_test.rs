@@ -1958,3 +1958,73 @@ fn flowchart_docs_math_fixture_renders_supported_ratex_formulas() {
         "expected supported flowchart fixture formulas to replace source delimiters: {svg}"
     );
 }
+
+fn svg_group_translate(svg: &str, open_tag_prefix: &str) -> Vec<(f64, f64)> {
+    svg.match_indices(open_tag_prefix)
+        .filter_map(|(start, _)| {
+            let tag = &svg[start..start + svg[start..].find('>')?];
+            let args = tag.split(r#"transform="translate("#).nth(1)?;
+            let args = &args[..args.find(')')?];
+            let (x, y) = args.split_once(',')?;
+            Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[test]
+fn flowchart_nested_subgraph_directions_match_upstream_svg_positions() {
+    // `flowchart LR` with a `direction TB` subgraph that nests a `direction RL` subgraph: each
+    // cluster interior is laid out in its own rank direction and embedded as a recursive root.
+    let stem = "stress_flowchart_subgraph_direction_nested_mixed_042";
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("fixtures");
+    let text = std::fs::read_to_string(fixtures.join("flowchart").join(format!("{stem}.mmd")))
+        .expect("read fixture .mmd");
+    let upstream = std::fs::read_to_string(
+        fixtures
+            .join("upstream-svgs")
+            .join("flowchart")
+            .join(format!("{stem}.svg")),
+    )
+    .expect("read upstream svg");
+
+    let engine = Engine::new();
+    let parsed = block_on(engine.parse_diagram(&text, ParseOptions::default()))
+        .expect("parse ok")
+        .expect("diagram detected");
+    let layout_options = LayoutOptions {
+        text_measurer: std::sync::Arc::new(VendoredFontMetricsTextMeasurer::default()),
+        ..Default::default()
+    };
+    let out = layout_parsed(&parsed, &layout_options).expect("layout ok");
+    let LayoutDiagram::FlowchartV2(layout) = out.layout else {
+        panic!("expected FlowchartV2 layout");
+    };
+    let svg = render_flowchart_v2_svg(
+        &layout,
+        &out.semantic,
+        &out.meta.effective_config,
+        out.meta.title.as_deref(),
+        layout_options.text_measurer.as_ref(),
+        &SvgRenderOptions {
+            diagram_id: Some(stem.to_string()),
+            ..SvgRenderOptions::default()
+        },
+    )
+    .expect("render svg");
+
+    assert_eq!(
+        svg_group_translate(&svg, r#"<g class="root""#),
+        svg_group_translate(&upstream, r#"<g class="root""#),
+        "recursive cluster roots should be embedded at the upstream offsets"
+    );
+    for node in ["t1", "t2", "i1", "i2"] {
+        let prefix = format!(r#"id="{stem}-flowchart-{node}-"#);
+        let ours = svg_group_translate(&svg, &prefix);
+        let theirs = svg_group_translate(&upstream, &prefix);
+        assert_eq!(ours.len(), 1, "node {node} should render once: {svg}");
+        assert_eq!(ours, theirs, "node {node} position should match upstream");
+    }
+}
@@ -187,6 +187,7 @@ fn fixtures_match_layout_golden_snapshots_when_present() {
                 &text,
                 ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
@@ -400,6 +400,38 @@ fn sequence_bracketed_block_titles_receive_the_renderer_bracket_pair() {
     );
 }
 
+// Mermaid's SequenceDB keeps block labels raw, exactly like message text (see the core
+// `special_characters_in_loop_opt_alt_par` test); both only become safe when `drawText` writes
+// them as text content. Pin that block labels share the message path instead of markup.
+#[test]
+fn sequence_block_labels_render_script_tags_as_escaped_text() {
+    let svg = render_sequence_svg_from_text(
+        r#"sequenceDiagram
+    loop <script>alert(1)</script>every minute
+        Alice->>Bob: <script>alert(2)</script>Ping
+    end
+    critical <script>alert(3)</script>connect
+        Alice->>Bob: Open
+    option <script>alert(4)</script>timeout
+        Alice->>Bob: Retry
+    end
+    break <script>alert(5)</script>failed
+        Alice->>Bob: Abort
+    end"#,
+    );
+
+    assert!(
+        !svg.contains("<script"),
+        "expected no live script elements in the sequence SVG: {svg}"
+    );
+    for n in 1..=5 {
+        assert!(
+            svg.contains(&format!("&lt;script>alert({n})&lt;/script>")),
+            "expected script tag {n} to be rendered as escaped text: {svg}"
+        );
+    }
+}
+
 #[test]
 fn sequence_autonumber_renders_decimal_sequence_numbers() {
     let svg = render_sequence_svg_from_text(
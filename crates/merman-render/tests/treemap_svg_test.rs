@@ -222,6 +222,7 @@ classDef c fill:#ff0000, stroke:rgb(1\,2\,3), color;
         source,
         ParseOptions {
             suppress_errors: true,
+            ..Default::default()
         },
     ))
     .expect("parse returns suppressed error")
@@ -530,6 +530,7 @@ fn bench_parse_typed(c: &mut Criterion) {
             config: pre.config.clone(),
             effective_config,
             title,
+            warnings: Vec::new(),
         };
 
         if merman_core::diagrams::class::parse_class_typed(&pre.code, &meta).is_err() {
@@ -561,6 +562,7 @@ fn bench_parse_typed(c: &mut Criterion) {
                     config: pre.config,
                     effective_config,
                     title,
+                    warnings: Vec::new(),
                 };
 
                 let parsed = merman_core::diagrams::class::parse_class_typed(&pre.code, &meta);
@@ -607,6 +609,7 @@ fn bench_parse_typed_only(c: &mut Criterion) {
             config: pre.config,
             effective_config,
             title,
+            warnings: Vec::new(),
         };
 
         // Pre-check typed parse viability once to keep the bench stable.
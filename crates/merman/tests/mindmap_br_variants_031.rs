@@ -9,6 +9,7 @@ fn mindmap_br_variants_031_matches_upstream_node_geometry() {
     let engine = merman_core::Engine::new();
     let parse_options = merman_core::ParseOptions {
         suppress_errors: false,
+        ..Default::default()
     };
 
     let layout = merman::render::LayoutOptions {
@@ -90,6 +90,7 @@ pub(crate) fn compare_c4_svgs(args: Vec<String>) -> Result<(), XtaskError> {
                 input.text,
                 merman::ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
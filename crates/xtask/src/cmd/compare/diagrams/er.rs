@@ -111,6 +111,7 @@ pub(crate) fn compare_er_svgs(args: Vec<String>) -> Result<(), XtaskError> {
                 input.text,
                 merman::ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
@@ -181,6 +181,7 @@ fn compare_stage_b_svgs(args: Vec<String>, spec: StageBCompareSpec) -> Result<()
                 input.text,
                 merman::ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
@@ -69,6 +69,7 @@ pub(crate) fn compare_radar_svgs(args: Vec<String>) -> Result<(), XtaskError> {
                 input.text,
                 merman::ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
@@ -71,6 +71,7 @@ pub(crate) fn compare_sequence_svgs(args: Vec<String>) -> Result<(), XtaskError>
     let engine = crate::cmd::svg_compare_engine();
     let parse_opts = merman::ParseOptions {
         suppress_errors: true,
+        ..Default::default()
     };
     let tools_root = crate::cmd::mermaid_cli_root();
     let toolchain_read_guard = crate::cmd::acquire_upstream_svg_toolchain_read_guard(&tools_root)?;
@@ -48,6 +48,7 @@ pub(crate) fn compare_treemap_svgs(args: Vec<String>) -> Result<(), XtaskError>
     let layout_opts = crate::cmd::svg_compare_layout_opts();
     let parse_opts = merman::ParseOptions {
         suppress_errors: true,
+        ..Default::default()
     };
     run_svg_compare(
         CompareRunOptions {
@@ -258,6 +258,7 @@ pub(crate) fn gen_svg_overrides(args: Vec<String>) -> Result<(), XtaskError> {
         let engine = merman::Engine::new();
         let parse_opts = merman::ParseOptions {
             suppress_errors: true,
+            ..Default::default()
         };
 
         let mut extra: Vec<String> = Vec::new();
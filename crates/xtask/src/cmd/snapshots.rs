@@ -227,6 +227,7 @@ pub(crate) fn update_layout_snapshots(args: Vec<String>) -> Result<(), XtaskErro
                 &text,
                 merman::ParseOptions {
                     suppress_errors: true,
+                    ..Default::default()
                 },
             )) {
                 Ok(Some(v)) => v,
@@ -765,6 +766,7 @@ pub(crate) fn update_snapshots(args: Vec<String>) -> Result<(), XtaskError> {
             &text,
             merman::ParseOptions {
                 suppress_errors: true,
+                ..Default::default()
             },
         )) {
             Ok(Some(v)) => v,